@@ -0,0 +1,62 @@
+use std::fmt;
+
+use crate::int_trait::Int;
+use crate::SmallerVec;
+
+// Reserves everything up front, then only ever writes into that capacity, so
+// nothing is reallocated between `new` and `finish`. Handy when a header says
+// how many elements are coming before they're parsed.
+pub struct SmallerVecBuilder<T, Limit: Int> {
+    vec: SmallerVec<T, Limit>,
+}
+
+impl<T, Limit: Int> SmallerVecBuilder<T, Limit> {
+    #[cfg(not(no_global_oom_handling))]
+    pub fn new(capacity: usize) -> Self {
+        SmallerVecBuilder {
+            vec: SmallerVec::with_capacity(capacity),
+        }
+    }
+
+    // hands `value` back if the reserved capacity is already used up
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        self.vec.push_within_capacity(value)
+    }
+
+    #[track_caller]
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            builder_full(self.vec.capacity());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    // how many more pushes fit in what was reserved
+    pub fn remaining(&self) -> usize {
+        self.vec.capacity() - self.vec.len()
+    }
+
+    pub fn finish(self) -> SmallerVec<T, Limit> {
+        self.vec
+    }
+}
+
+impl<T: fmt::Debug, Limit: Int> fmt::Debug for SmallerVecBuilder<T, Limit> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmallerVecBuilder").field(&&*self.vec).finish()
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn builder_full(capacity: usize) -> ! {
+    panic!("push past the reserved capacity of {capacity}");
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ptr::{self, NonNull};
+use std::slice;
+
+use crate::int_trait::Int;
+use crate::SmallerVec;
+
+// The vec's len is cut back to the start of the drained range while this is
+// alive, so leaking a `Drain` only leaks the drained elements and the tail
+// rather than leaving the vec pointing at moved out values.
+pub struct Drain<'a, T, Limit: Int> {
+    pub(crate) vec: NonNull<SmallerVec<T, Limit>>,
+    pub(crate) iter: slice::Iter<'a, T>,
+    pub(crate) tail_start: usize,
+    pub(crate) tail_len: usize,
+    pub(crate) _marker: PhantomData<&'a mut SmallerVec<T, Limit>>,
+}
+
+impl<T, Limit: Int> Drain<'_, T, Limit> {
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_slice()
+    }
+
+    // stops draining, the elements not yet yielded stay in the vec in order
+    pub fn keep_rest(self) {
+        let mut this = ManuallyDrop::new(self);
+        unsafe {
+            let vec = this.vec.as_mut();
+            let start = vec.len();
+            let unyielded = this.iter.len();
+            let base = vec.ptr.as_ptr();
+            if unyielded != 0 {
+                let offset = unyielded_offset(&this.iter, base);
+                ptr::copy(base.add(offset), base.add(start), unyielded);
+            }
+            ptr::copy(base.add(this.tail_start), base.add(start + unyielded), this.tail_len);
+            vec.len = Limit::from_usize(start + unyielded + this.tail_len);
+        }
+    }
+}
+
+// Index of the first element `iter` hasn't yielded in the buffer at `base`.
+// `offset_from` refuses ZSTs, but they all sit at the same address and nothing
+// is actually read or moved for them, so any index does.
+unsafe fn unyielded_offset<T>(iter: &slice::Iter<'_, T>, base: *const T) -> usize {
+    if mem::size_of::<T>() == 0 {
+        0
+    } else {
+        iter.as_slice().as_ptr().offset_from(base) as usize
+    }
+}
+
+impl<T: fmt::Debug, Limit: Int> fmt::Debug for Drain<'_, T, Limit> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.iter.as_slice()).finish()
+    }
+}
+
+impl<T, Limit: Int> Iterator for Drain<'_, T, Limit> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|elt| unsafe { ptr::read(elt) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, Limit: Int> DoubleEndedIterator for Drain<'_, T, Limit> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|elt| unsafe { ptr::read(elt) })
+    }
+}
+
+impl<T, Limit: Int> ExactSizeIterator for Drain<'_, T, Limit> {}
+
+impl<T, Limit: Int> FusedIterator for Drain<'_, T, Limit> {}
+
+impl<T, Limit: Int> Drop for Drain<'_, T, Limit> {
+    fn drop(&mut self) {
+        // moves the tail back down even if dropping an element panics
+        struct DropGuard<'r, 'a, T, Limit: Int>(&'r mut Drain<'a, T, Limit>);
+
+        impl<T, Limit: Int> Drop for DropGuard<'_, '_, T, Limit> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                unsafe {
+                    let vec = drain.vec.as_mut();
+                    let start = vec.len();
+                    if drain.tail_len != 0 && drain.tail_start != start {
+                        let base = vec.ptr.as_ptr();
+                        ptr::copy(base.add(drain.tail_start), base.add(start), drain.tail_len);
+                    }
+                    vec.len = Limit::from_usize(start + drain.tail_len);
+                    vec.check_invariants();
+                }
+            }
+        }
+
+        let iter = std::mem::take(&mut self.iter);
+        let remaining = iter.len();
+        let guard = DropGuard(self);
+        if remaining == 0 {
+            return;
+        }
+        unsafe {
+            // go back through the vec's own pointer, the slice iterator only
+            // ever hands out shared references
+            let vec = guard.0.vec.as_mut();
+            let offset = unyielded_offset(&iter, vec.ptr.as_ptr());
+            let to_drop = ptr::slice_from_raw_parts_mut(vec.ptr.as_ptr().add(offset), remaining);
+            ptr::drop_in_place(to_drop);
+        }
+    }
+}
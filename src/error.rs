@@ -0,0 +1,86 @@
+use std::alloc::Layout;
+use std::fmt;
+
+// returned when a length doesn't fit, without anything having been allocated.
+// `max` is whichever ceiling was hit, the capacity or `Limit::MAX`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapacityError {
+    requested: usize,
+    max: usize,
+}
+
+impl CapacityError {
+    pub(crate) fn new(requested: usize, max: usize) -> Self {
+        CapacityError { requested, max }
+    }
+
+    // the length that was attempted
+    pub fn requested(&self) -> usize {
+        self.requested
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "length {} exceeds the maximum of {}", self.requested, self.max)
+    }
+}
+
+impl std::error::Error for CapacityError {}
+
+// returned by `try_reserve` and friends. Nothing about the vec changes when
+// this comes back, the old buffer and its contents are still there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryReserveError {
+    kind: TryReserveErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveErrorKind {
+    // the element count doesn't fit in `Limit`
+    CapacityOverflow { requested: usize, max: usize },
+    // the count fits in `Limit` but its byte size doesn't fit in an `isize`
+    AllocationTooLarge { requested: usize },
+    // the allocator returned null
+    AllocError { layout: Layout },
+}
+
+impl TryReserveError {
+    pub(crate) fn capacity_overflow(requested: usize, max: usize) -> Self {
+        TryReserveError { kind: TryReserveErrorKind::CapacityOverflow { requested, max } }
+    }
+
+    pub(crate) fn allocation_too_large(requested: usize) -> Self {
+        TryReserveError { kind: TryReserveErrorKind::AllocationTooLarge { requested } }
+    }
+
+    pub(crate) fn alloc_error(layout: Layout) -> Self {
+        TryReserveError { kind: TryReserveErrorKind::AllocError { layout } }
+    }
+
+    pub fn kind(&self) -> TryReserveErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TryReserveErrorKind::CapacityOverflow { requested, max } => {
+                write!(f, "capacity overflow: requested {requested} elements, max {max}")
+            }
+            TryReserveErrorKind::AllocationTooLarge { requested } => {
+                write!(f, "allocation too large: {requested} elements exceed isize::MAX bytes")
+            }
+            TryReserveErrorKind::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
@@ -0,0 +1,112 @@
+use std::ptr;
+use std::slice;
+
+use crate::int_trait::Int;
+use crate::SmallerVec;
+
+// The vec's len is zeroed while this is alive, so leaking it only leaks the
+// elements rather than exposing extracted ones. Dropping it (or `keep_rest`)
+// keeps everything that wasn't visited yet.
+pub struct ExtractIf<'a, T, Limit: Int, F> {
+    pub(crate) vec: &'a mut SmallerVec<T, Limit>,
+    // next element to hand to `pred`
+    pub(crate) idx: usize,
+    // one past the last element not yet handed to `pred` from the back
+    pub(crate) end: usize,
+    // how many elements have been extracted from the front so far
+    pub(crate) del: usize,
+    // and from the back, the kept ones behind `end` sit this far above it
+    pub(crate) back_del: usize,
+    pub(crate) old_len: usize,
+    pub(crate) pred: F,
+}
+
+impl<T, Limit: Int, F> ExtractIf<'_, T, Limit, F> {
+    // stops extracting, every element not yet visited stays in the vec
+    pub fn keep_rest(self) {
+        drop(self)
+    }
+}
+
+impl<T, Limit: Int, F> Iterator for ExtractIf<'_, T, Limit, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.end {
+                let i = self.idx;
+                let v = slice::from_raw_parts_mut(self.vec.ptr.as_ptr(), self.old_len);
+                let drained = (self.pred)(&mut v[i]);
+                // only advance once `pred` returned, a panic leaves the
+                // element to be kept by `drop`
+                self.idx += 1;
+                if drained {
+                    self.del += 1;
+                    return Some(ptr::read(&v[i]));
+                } else if self.del > 0 {
+                    let del = self.del;
+                    let src: *const T = &v[i];
+                    let dst: *mut T = &mut v[i - del];
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+// Kept elements from the back scan are shifted up towards the tail, so the
+// holes they leave end up right behind `end`
+impl<T, Limit: Int, F> DoubleEndedIterator for ExtractIf<'_, T, Limit, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn next_back(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.end {
+                let i = self.end - 1;
+                let v = slice::from_raw_parts_mut(self.vec.ptr.as_ptr(), self.old_len);
+                let drained = (self.pred)(&mut v[i]);
+                self.end -= 1;
+                if drained {
+                    self.back_del += 1;
+                    return Some(ptr::read(&v[i]));
+                } else if self.back_del > 0 {
+                    let back_del = self.back_del;
+                    let src: *const T = &v[i];
+                    let dst: *mut T = &mut v[i + back_del];
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+}
+
+impl<T, Limit: Int, F> Drop for ExtractIf<'_, T, Limit, F> {
+    fn drop(&mut self) {
+        unsafe {
+            let base = self.vec.ptr.as_ptr();
+            // close the front holes over the unvisited elements, then the
+            // back holes over the kept back elements and the tail
+            if self.del > 0 {
+                let src = base.add(self.idx);
+                ptr::copy(src, src.sub(self.del), self.end - self.idx);
+            }
+            let kept_end = self.end + self.back_del;
+            if self.del + self.back_del > 0 {
+                let src = base.add(kept_end);
+                ptr::copy(src, base.add(self.end - self.del), self.old_len - kept_end);
+            }
+            self.vec.len = Limit::from_usize(self.old_len - self.del - self.back_del);
+        }
+        self.vec.check_invariants();
+    }
+}
@@ -9,9 +9,28 @@ pub trait Int: Sized + Copy + PartialEq {
     fn half(self) -> Self;
     fn saturating_add(self, rhs: Self) -> Self;
     fn saturating_mul(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn max(self, rhs: Self) -> Self;
+    fn min(self, rhs: Self) -> Self;
     fn from_usize(val: usize) -> Self;
 }
 
+// Trait methods can't be called in const fns, but `Int` can't be implemented
+// outside the crate and every impl is a plain unsigned integer, so the value
+// can be read back by its size instead.
+pub(crate) const fn const_as_usize<I: Int>(val: &I) -> usize {
+    let ptr = val as *const I;
+    unsafe {
+        match core::mem::size_of::<I>() {
+            1 => *(ptr as *const u8) as usize,
+            2 => *(ptr as *const u16) as usize,
+            4 => *(ptr as *const u32) as usize,
+            8 => *(ptr as *const u64) as usize,
+            _ => panic!("unsupported Int size"),
+        }
+    }
+}
+
 macro_rules! impl_int {
     ($t:ty) => {
         impl crate::Int for $t {
@@ -43,6 +62,18 @@ macro_rules! impl_int {
                 self.saturating_add(rhs)
             }
             #[inline]
+            fn saturating_sub(self, rhs: Self) -> Self {
+                self.saturating_sub(rhs)
+            }
+            #[inline]
+            fn max(self, rhs: Self) -> Self {
+                core::cmp::Ord::max(self, rhs)
+            }
+            #[inline]
+            fn min(self, rhs: Self) -> Self {
+                core::cmp::Ord::min(self, rhs)
+            }
+            #[inline]
             fn from_usize(val: usize) -> Self {
                 val as Self
             }
@@ -51,7 +82,12 @@ macro_rules! impl_int {
 }
 
 #[cfg(target_pointer_width = "128")]
-compile_error!("currently unsupported");
+mod b128impl {
+    impl_int!(u64);
+    impl_int!(u32);
+    impl_int!(u16);
+    impl_int!(u8);
+}
 
 #[cfg(target_pointer_width = "64")]
 mod b64impl {
@@ -68,3 +104,33 @@ mod b32impl {
 
 #[cfg(target_pointer_width = "16")]
 compile_error!("currently unsupported");
+
+#[cfg(test)]
+mod tests {
+    use super::Int;
+
+    // goes through the trait rather than the inherent methods of the same name
+    fn check<I: Int + core::fmt::Debug>() {
+        let two = I::ONE.add(I::ONE);
+        assert_eq!(Int::saturating_sub(two, I::ONE), I::ONE);
+        assert_eq!(Int::saturating_sub(I::ONE, two), I::ZERO);
+        assert_eq!(Int::saturating_sub(I::ZERO, I::MAX), I::ZERO);
+        assert_eq!(Int::saturating_sub(I::MAX, I::MAX), I::ZERO);
+
+        assert_eq!(Int::max(I::ONE, two), two);
+        assert_eq!(Int::max(two, I::ONE), two);
+        assert_eq!(Int::max(I::MAX, I::ZERO), I::MAX);
+        assert_eq!(Int::min(I::ONE, two), I::ONE);
+        assert_eq!(Int::min(two, I::ONE), I::ONE);
+        assert_eq!(Int::min(I::MAX, I::ZERO), I::ZERO);
+        assert_eq!(Int::min(I::MAX, I::MAX), I::MAX);
+    }
+
+    #[test]
+    fn saturating_sub_max_min() {
+        check::<u8>();
+        check::<u16>();
+        #[cfg(any(target_pointer_width = "64", target_pointer_width = "128"))]
+        check::<u32>();
+    }
+}
@@ -1,13 +1,33 @@
+mod builder;
+mod drain;
+mod error;
+mod extract_if;
 mod int_trait;
 mod raw_vec;
+#[cfg(feature = "stats")]
+pub mod stats;
+// the integration tests' counting allocator, for unit tests of the private
+// buffer code
+#[cfg(test)]
+#[path = "../tests/common/mod.rs"]
+mod common;
 
 // provides a small size optimized vec
 
+pub use builder::SmallerVecBuilder;
+pub use drain::Drain;
+pub use error::{CapacityError, TryReserveError, TryReserveErrorKind};
+pub use extract_if::ExtractIf;
 use int_trait::Int;
 use std::alloc::{self, Layout};
-use std::mem::ManuallyDrop;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::{Bound, Range, RangeBounds};
 use std::ptr;
 use std::ptr::NonNull;
+use std::sync::Arc;
+use std::slice::SliceIndex;
 
 // could make for smaller ourter structs by allowing users to mesh data into a T
 // stored in the vec
@@ -41,165 +61,1733 @@ use std::ptr::NonNull;
 
 
 
-#[derive(Debug, Clone)]
-pub struct SmallerVec<T, Limit: Int> {
+// u32 isn't a valid limit on 32 bit targets so the default shrinks with usize
+#[cfg(any(target_pointer_width = "64", target_pointer_width = "128"))]
+pub type DefaultLimit = u32;
+#[cfg(target_pointer_width = "32")]
+pub type DefaultLimit = u16;
+
+/// The layout is stable and matches the C struct `{ T *ptr; Limit len; Limit cap; }`,
+/// so C code can read the pointer and length directly. `ptr` is dangling (but
+/// aligned) while nothing is allocated: when `cap` is 0, and always for
+/// zero-sized `T`, whose `cap` is `Limit::MAX`.
+#[derive(Debug)]
+#[repr(C)]
+pub struct SmallerVec<T, Limit: Int = DefaultLimit> {
     ptr: NonNull<T>,
     len: Limit,
     cap: Limit,
 }
 
-impl<T, Limit: Int> SmallerVec<T, Limit> {
+// `NonNull` opts out of both, but the vec uniquely owns its buffer the way a
+// `Box<[T]>` does: nothing else points into it, and `&self` only ever hands
+// out `&T`. So it's exactly as thread safe as the `T`s it holds.
+unsafe impl<T: Send, Limit: Int> Send for SmallerVec<T, Limit> {}
+unsafe impl<T: Sync, Limit: Int> Sync for SmallerVec<T, Limit> {}
+
+// How `shrink_to_fit_with` gives memory back. Allocators can decline to shrink
+// a block in place, or do it by splitting and leave the freed part fragmented,
+// so when most of a buffer is going away a fresh tight block plus a copy is
+// often the better deal. `Auto` (what `shrink_to_fit` uses) does that once
+// less than a quarter of the capacity is in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShrinkStrategy {
+    #[default]
+    Auto,
+    Realloc,
+    Fresh,
+}
+
+pub type SmallVec8<T> = SmallerVec<T, u8>;
+pub type SmallVec16<T> = SmallerVec<T, u16>;
+#[cfg(any(target_pointer_width = "64", target_pointer_width = "128"))]
+pub type SmallVec32<T> = SmallerVec<T, u32>;
+#[cfg(target_pointer_width = "128")]
+pub type SmallVec64<T> = SmallerVec<T, u64>;
+
+// `SmallerVec::with_limit::<u16>()` picks the limit up front and leaves `T` to
+// be inferred from use, instead of `SmallerVec::<_, u16>::new()`
+impl<T> SmallerVec<T> {
+    pub const fn with_limit<L: Int>() -> SmallerVec<T, L> {
+        SmallerVec::new()
+    }
+}
+
+impl<T, Limit: Int> SmallerVec<T, Limit> {
+
+    const FIRST_ALLOC_SIZE: usize = match core::mem::size_of::<T>()  {
+        1 => 8,
+        i if i <= 1024 => 4,
+        _ => 1,
+    };
+
+    const IS_ZST: bool = core::mem::size_of::<T>() == 0;
+
+    // with `debug-invariants` on, every mutating method checks the vec is
+    // still well formed on the way in and out. Compiled out otherwise
+    #[inline(always)]
+    pub(crate) fn check_invariants(&self) {
+        #[cfg(feature = "debug-invariants")]
+        {
+            debug_assert!(
+                self.len() <= self.capacity(),
+                "len {} is past cap {}",
+                self.len(),
+                self.capacity()
+            );
+            debug_assert!(self.capacity() <= Limit::MAX.as_usize());
+            debug_assert!(
+                !Self::IS_ZST || self.cap == Limit::MAX,
+                "zero sized elements with cap {}",
+                self.capacity()
+            );
+            debug_assert!(
+                self.cap == Limit::ZERO || Self::IS_ZST || self.ptr != NonNull::dangling(),
+                "cap {} with a dangling pointer",
+                self.capacity()
+            );
+        }
+    }
+
+    pub const fn new() -> Self {
+        Self::new_unallocated()
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec = Self::new();
+        vec.reserve_exact(capacity);
+        vec
+    }
+
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut vec = Self::new();
+        vec.try_reserve_exact(capacity)?;
+        Ok(vec)
+    }
+    #[cfg(not(no_global_oom_handling))]
+    #[track_caller]
+    pub fn push(&mut self, value: T) {
+        self.check_invariants();
+        if self.len == self.cap {
+            self.grow();
+        }
+
+        unsafe {
+            core::ptr::write(self.ptr.as_ptr().add(self.len.as_usize()), value);
+        }
+
+        self.len = self.len.add(Limit::ONE);
+        self.check_invariants();
+    }
+
+    /// Grows by one slot and hands it out uninitialized, with `len` already
+    /// covering it.
+    ///
+    /// # Safety
+    ///
+    /// The slot must be written before the vec is used again in any way that
+    /// can read or drop it (indexing, iterating, `pop`, `truncate`, dropping
+    /// the vec, ...). If it won't be initialized after all, take it back out
+    /// with `set_len(len() - 1)` rather than `pop`.
+    #[cfg(not(no_global_oom_handling))]
+    pub unsafe fn push_uninit(&mut self) -> &mut MaybeUninit<T> {
+        self.check_invariants();
+        if self.len == self.cap {
+            self.grow();
+        }
+        let slot = self.ptr.as_ptr().add(self.len.as_usize()) as *mut MaybeUninit<T>;
+        self.len = self.len.add(Limit::ONE);
+        self.check_invariants();
+        &mut *slot
+    }
+
+    // never reallocates, hands `value` back once the capacity is used up
+    pub fn push_within_capacity(&mut self, value: T) -> Result<(), T> {
+        self.check_invariants();
+        if self.len == self.cap {
+            return Err(value);
+        }
+        unsafe {
+            ptr::write(self.ptr.as_ptr().add(self.len.as_usize()), value);
+        }
+        self.len = self.len.add(Limit::ONE);
+        self.check_invariants();
+        Ok(())
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    pub fn pop(&mut self) -> Option<T> {
+        self.check_invariants();
+        if self.len == Limit::ZERO {
+            None
+        } else {
+            self.len = self.len.sub(Limit::ONE);
+            self.check_invariants();
+            unsafe { Some(core::ptr::read(self.ptr.as_ptr().add(self.len.as_usize()))) }
+        }
+    }
+
+    // O(n), everything after the first element shifts down one
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.try_remove(0)
+    }
+
+    // reversed in place, the buffer is reused as is
+    pub fn into_reversed(mut self) -> Self {
+        self.reverse();
+        self
+    }
+
+    // the head by value and the rest in the same buffer
+    pub fn into_first_rest(mut self) -> Option<(T, Self)> {
+        let first = self.pop_first()?;
+        Some((first, self))
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_last_rest(mut self) -> Option<(T, Self)> {
+        let last = self.pop()?;
+        Some((last, self))
+    }
+
+    // takes up to `n` elements off the end, keeping their order
+    #[cfg(not(no_global_oom_handling))]
+    pub fn pop_n(&mut self, n: usize) -> SmallerVec<T, Limit> {
+        self.check_invariants();
+        let len = self.len();
+        let n = n.min(len);
+        let mut tail = SmallerVec::with_capacity(n);
+        unsafe {
+            self.len = Limit::from_usize(len - n);
+            ptr::copy_nonoverlapping(self.ptr.as_ptr().add(len - n), tail.ptr.as_ptr(), n);
+            tail.set_len(n);
+        }
+        self.check_invariants();
+        tail
+    }
+
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            unsafe { Some(&*self.ptr.as_ptr()) }
+        }
+    }
+
+    #[inline]
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            None
+        } else {
+            unsafe { Some(&mut *self.ptr.as_ptr()) }
+        }
+    }
+
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            unsafe { Some(&*self.ptr.as_ptr().add(self.len() - 1)) }
+        }
+    }
+
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            None
+        } else {
+            unsafe { Some(&mut *self.ptr.as_ptr().add(self.len() - 1)) }
+        }
+    }
+
+    #[track_caller]
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let len = self.len();
+        if a >= len || b >= len {
+            swap_failed(a, b, len);
+        }
+        unsafe {
+            let base = self.ptr.as_ptr();
+            ptr::swap(base.add(a), base.add(b));
+        }
+    }
+
+    // generic over `SliceIndex` like the slice versions so ranges keep working
+    #[inline]
+    pub fn get<I: SliceIndex<[T]>>(&self, index: I) -> Option<&I::Output> {
+        (**self).get(index)
+    }
+
+    #[inline]
+    pub fn get_mut<I: SliceIndex<[T]>>(&mut self, index: I) -> Option<&mut I::Output> {
+        (**self).get_mut(index)
+    }
+
+    /// # Safety
+    /// `index` must be in bounds
+    #[inline]
+    pub unsafe fn get_unchecked<I: SliceIndex<[T]>>(&self, index: I) -> &I::Output {
+        (**self).get_unchecked(index)
+    }
+
+    /// # Safety
+    /// `index` must be in bounds
+    #[inline]
+    pub unsafe fn get_unchecked_mut<I: SliceIndex<[T]>>(&mut self, index: I) -> &mut I::Output {
+        (**self).get_unchecked_mut(index)
+    }
+
+    // the buffer pointer, dangling but aligned while nothing is allocated
+    #[inline]
+    pub fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+
+    #[inline]
+    pub fn non_null_mut(&mut self) -> NonNull<T> {
+        self.ptr
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        int_trait::const_as_usize(&self.cap)
+    }
+
+    // ZSTs always report 0 since they never take up any room
+    #[inline]
+    pub const fn capacity_in_bytes(&self) -> usize {
+        self.capacity() * core::mem::size_of::<T>()
+    }
+
+    // the size of the heap allocation backing the vec, 0 when there isn't one
+    #[inline]
+    pub const fn allocation_size(&self) -> usize {
+        if Self::IS_ZST || self.capacity() == 0 {
+            0
+        } else {
+            self.capacity_in_bytes()
+        }
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        int_trait::const_as_usize(&self.len)
+    }
+    // the length as another limit sized integer, if it fits
+    #[inline]
+    pub fn len_as<L2: Int>(&self) -> Option<L2> {
+        let len = self.len();
+        if len > L2::MAX.as_usize() {
+            None
+        } else {
+            Some(L2::from_usize(len))
+        }
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // a no-op when already tight, an empty vec goes back to being unallocated
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to_fit_with(ShrinkStrategy::Auto)
+    }
+
+    pub fn shrink_to_fit_with(&mut self, strategy: ShrinkStrategy) {
+        self.check_invariants();
+        if self.cap == self.len {
+            return;
+        }
+        let fresh = match strategy {
+            ShrinkStrategy::Auto => self.len() < self.capacity() / 4,
+            ShrinkStrategy::Realloc => false,
+            ShrinkStrategy::Fresh => true,
+        };
+        self.shrink_to_cap(self.len, fresh);
+    }
+
+    // shrinks the buffer to fit and hands it over, no elements are copied
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        Vec::from(self).into_boxed_slice()
+    }
+
+    // `Arc` keeps its counts in front of the data, so this costs exactly one
+    // move of the elements into a new allocation (never a clone)
+    pub fn into_arc_slice(self) -> Arc<[T]> {
+        Arc::from(Vec::from(self))
+    }
+
+    // the same single move as `into_arc_slice` in two visible steps: the box
+    // only trims the buffer in place, and `Arc::from` is where the elements
+    // get moved in behind the counts
+    pub fn into_boxed_then_arc(self) -> Arc<[T]> {
+        Arc::from(self.into_boxed_slice())
+    }
+
+    // takes over the `Vec`'s buffer when its capacity fits in `Limit`,
+    // otherwise the elements are moved into a fresh one
+    #[cfg(not(no_global_oom_handling))]
+    fn from_vec(vec: Vec<T>) -> Self {
+        let len = vec.len();
+        if len > Limit::MAX.as_usize() {
+            capacity_overflow::<Limit>(len)
+        }
+        let mut vec = ManuallyDrop::new(vec);
+        if Self::IS_ZST || vec.capacity() > Limit::MAX.as_usize() {
+            let mut out = Self::with_capacity(len);
+            unsafe {
+                ptr::copy_nonoverlapping(vec.as_ptr(), out.ptr.as_ptr(), len);
+                out.set_len(len);
+                vec.set_len(0);
+                ManuallyDrop::drop(&mut vec);
+            }
+            return out;
+        }
+        SmallerVec {
+            ptr: unsafe { NonNull::new_unchecked(vec.as_mut_ptr()) },
+            len: Limit::from_usize(len),
+            cap: Limit::from_usize(vec.capacity()),
+        }
+    }
+
+    // (ptr, len, cap) with the counts kept in `Limit`, the buffer is leaked
+    // until it's handed back to `from_parts`
+    pub fn into_parts(self) -> (NonNull<T>, Limit, Limit) {
+        let vec = ManuallyDrop::new(self);
+        (vec.ptr, vec.len, vec.cap)
+    }
+
+    /// # Safety
+    ///
+    /// The parts must have come from `into_parts` on a `SmallerVec<T, Limit>`
+    /// (or describe a buffer allocated the same way, with `len <= cap` and
+    /// the first `len` elements initialized). Ownership of the buffer moves
+    /// into the returned vec.
+    pub unsafe fn from_parts(ptr: NonNull<T>, len: Limit, cap: Limit) -> Self {
+        // left undropped if the check fails, parts that don't add up can't be
+        // freed safely either
+        let vec = ManuallyDrop::new(SmallerVec { ptr, len, cap });
+        vec.check_invariants();
+        ManuallyDrop::into_inner(vec)
+    }
+
+    // moves to a limit at least as wide as this one, the buffer is kept as is
+    pub fn widen_limit<L2: Int>(self) -> SmallerVec<T, L2> {
+        const {
+            assert!(
+                core::mem::size_of::<L2>() >= core::mem::size_of::<Limit>(),
+                "widen_limit needs a limit at least as wide as the current one"
+            )
+        };
+        let vec = ManuallyDrop::new(self);
+        SmallerVec {
+            ptr: vec.ptr,
+            len: L2::from_usize(vec.len()),
+            cap: if Self::IS_ZST { L2::MAX } else { L2::from_usize(vec.capacity()) },
+        }
+    }
+
+    // moves to a narrower limit, handing the vec back if its len doesn't fit.
+    // The buffer is kept if the capacity fits too, otherwise it's shrunk to
+    // the most `L2` can describe.
+    pub fn shrink_limit<L2: Int>(mut self) -> Result<SmallerVec<T, L2>, Self> {
+        if self.len() > L2::MAX.as_usize() {
+            return Err(self);
+        }
+        if self.capacity() > L2::MAX.as_usize() {
+            self.shrink_to_cap(Limit::from_usize(L2::MAX.as_usize()), false);
+        }
+        let vec = ManuallyDrop::new(self);
+        Ok(SmallerVec {
+            ptr: vec.ptr,
+            len: L2::from_usize(vec.len()),
+            cap: if Self::IS_ZST { L2::MAX } else { L2::from_usize(vec.capacity()) },
+        })
+    }
+
+    /// # Safety
+    /// `new_len` must be <= `capacity()` and the elements up to it initialized
+    #[inline]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        self.len = Limit::from_usize(new_len);
+        self.check_invariants();
+    }
+
+    /// Drops everything past `len`, front to back. This is the same order `Vec`
+    /// uses, and the order `clear` and dropping the whole vec use too.
+    pub fn truncate(&mut self, len: usize) -> &mut Self {
+        self.check_invariants();
+        let old_len = self.len();
+        if len >= old_len {
+            return self;
+        }
+        unsafe {
+            self.len = Limit::from_usize(len);
+            let tail = ptr::slice_from_raw_parts_mut(self.ptr.as_ptr().add(len), old_len - len);
+            ptr::drop_in_place(tail);
+        }
+        self.check_invariants();
+        self
+    }
+
+    /// Like `truncate` but drops back to front, the order a loop of `pop`s gives.
+    pub fn truncate_rev(&mut self, len: usize) -> &mut Self {
+        self.check_invariants();
+        while self.len() > len {
+            unsafe {
+                self.len = self.len.sub(Limit::ONE);
+                ptr::drop_in_place(self.ptr.as_ptr().add(self.len()));
+            }
+        }
+        self.check_invariants();
+        self
+    }
+
+    #[inline]
+    pub fn clear(&mut self) -> &mut Self {
+        self.truncate(0)
+    }
+
+    /// Like `set_len` but refuses lengths past `capacity()`, and drops the
+    /// elements cut off when shrinking.
+    ///
+    /// # Safety
+    /// when growing, the elements between `len()` and `new_len` must already
+    /// be initialized
+    pub unsafe fn set_len_checked(&mut self, new_len: usize) -> Result<(), CapacityError> {
+        self.check_invariants();
+        let len = self.len();
+        if new_len > self.capacity() {
+            return Err(CapacityError::new(new_len, self.capacity()));
+        }
+        if new_len < len {
+            self.truncate(new_len);
+        } else {
+            self.len = Limit::from_usize(new_len);
+        }
+        self.check_invariants();
+        Ok(())
+    }
+
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.ptr.as_ptr().add(self.len()) as *mut MaybeUninit<T>,
+                self.capacity() - self.len(),
+            )
+        }
+    }
+
+    pub fn spare_capacity(&self) -> &[MaybeUninit<T>] {
+        unsafe {
+            std::slice::from_raw_parts(
+                self.ptr.as_ptr().add(self.len()) as *const MaybeUninit<T>,
+                self.capacity() - self.len(),
+            )
+        }
+    }
+
+    /// Reserves `additional` then hands that much spare capacity to `f`, which
+    /// returns how many of the leading slots it initialized. A count past
+    /// `additional` panics, but nothing can check the slots below it were
+    /// really written, which is why this is `unsafe`.
+    ///
+    /// # Safety
+    /// `f` must have initialized at least as many leading slots as it returns
+    #[cfg(not(no_global_oom_handling))]
+    pub unsafe fn write_to_spare<F: FnOnce(&mut [MaybeUninit<T>]) -> usize>(&mut self, additional: usize, f: F) {
+        self.reserve(additional);
+        let written = f(&mut self.spare_capacity_mut()[..additional]);
+        assert!(written <= additional, "wrote {written} of {additional} spare slots");
+        self.set_len(self.len() + written);
+    }
+
+    // zero sized types never allocate, so the only thing bounding them is
+    // `Limit` and they start out (and stay) at a capacity of `Limit::MAX`
+    const fn new_unallocated() -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            len: Int::ZERO,
+            cap: if Self::IS_ZST { Limit::MAX } else { Int::ZERO },
+        }
+    }
+
+    // This can't overflow an isize since self.cap.bits < isize::BITS.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    fn growth_factor(&self) -> Limit {
+        // self.cap.saturating_add(self.cap.sub(self.cap.half()))
+        self.cap.saturating_add(self.cap)
+    }
+    #[cfg(not(no_global_oom_handling))]
+    fn grow(&mut self) {
+        // failout if allocation is already at the max
+        if self.cap == Limit::MAX {
+            capacity_overflow::<Limit>(self.cap.as_usize() + 1)
+        }
+        // never step up to less than the first allocation would be, so a cap
+        // left tiny by `shrink_to_fit` doesn't crawl back up 1, 2, 4, ...
+        let new_cap = self.growth_factor().as_usize().max(Self::FIRST_ALLOC_SIZE);
+        self.grow_exact(new_cap);
+    }
+
+    // reallocates to exactly `new_cap` (never rounding up), which must be
+    // larger than the current cap
+    #[cfg(not(no_global_oom_handling))]
+    fn grow_exact(&mut self, new_cap: usize) {
+        match self.try_grow_exact(new_cap) {
+            Ok(()) => {}
+            Err(e) => match e.kind() {
+                TryReserveErrorKind::CapacityOverflow { .. } => capacity_overflow::<Limit>(new_cap),
+                TryReserveErrorKind::AllocationTooLarge { .. } => allocation_too_large::<T>(new_cap),
+                TryReserveErrorKind::AllocError { layout } => alloc::handle_alloc_error(layout),
+            },
+        }
+    }
+
+    // the fallible core of `grow_exact`, on error the vec is left untouched
+    fn try_grow_exact(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        if new_cap > Limit::MAX.as_usize() {
+            return Err(TryReserveError::capacity_overflow(new_cap, Limit::MAX.as_usize()));
+        }
+        // zero sized types are already at `Limit::MAX`
+        if Self::IS_ZST {
+            return Ok(());
+        }
+
+        // the count fits in `Limit`, but a large enough `T` can still push the
+        // byte size past `isize::MAX`, which `Layout::array` rejects
+        let new_layout = match Layout::array::<T>(new_cap) {
+            Ok(layout) => layout,
+            Err(_) => return Err(TryReserveError::allocation_too_large(new_cap)),
+        };
+
+        // `realloc` extends in place when the allocator can, so growing never
+        // copies unless the block really has to move
+        let new_ptr = if self.cap == Limit::ZERO {
+            unsafe { alloc::alloc(new_layout) }
+        } else {
+            let old_layout = Layout::array::<T>(self.cap.as_usize()).unwrap() ;
+            let old_ptr = self.ptr.as_ptr() as *mut u8;
+            unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) }
+        };
+
+        // a failed realloc leaves the old block alone, so the vec is intact
+        self.ptr = match NonNull::new(new_ptr as *mut T) {
+            Some(p) => p,
+            None => return Err(TryReserveError::alloc_error(new_layout)),
+        };
+        #[cfg(feature = "stats")]
+        stats::record_realloc();
+        self.cap = Limit::from_usize(new_cap);
+        self.check_invariants();
+        Ok(())
+    }
+
+    // reallocates down to exactly `new_cap`, which must be >= len. `fresh`
+    // copies into a new block instead of going through `realloc`
+    fn shrink_to_cap(&mut self, new_cap: Limit, fresh: bool) {
+        debug_assert!(new_cap.as_usize() >= self.len());
+        if Self::IS_ZST {
+            return;
+        }
+        if self.cap == Limit::ZERO {
+            self.cap = new_cap;
+            return;
+        }
+        let old_layout = Layout::array::<T>(self.cap.as_usize()).unwrap();
+        if new_cap == Limit::ZERO {
+            unsafe { alloc::dealloc(self.ptr.as_ptr() as *mut u8, old_layout) };
+            self.ptr = NonNull::dangling();
+        } else {
+            let new_layout = Layout::array::<T>(new_cap.as_usize()).unwrap();
+            let new_ptr = if fresh {
+                unsafe {
+                    let new_ptr = alloc::alloc(new_layout);
+                    if !new_ptr.is_null() {
+                        ptr::copy_nonoverlapping(self.ptr.as_ptr(), new_ptr as *mut T, self.len());
+                        alloc::dealloc(self.ptr.as_ptr() as *mut u8, old_layout);
+                    }
+                    new_ptr
+                }
+            } else {
+                unsafe { alloc::realloc(self.ptr.as_ptr() as *mut u8, old_layout, new_layout.size()) }
+            };
+            self.ptr = match NonNull::new(new_ptr as *mut T) {
+                Some(p) => p,
+                None => alloc::handle_alloc_error(new_layout),
+            };
+        }
+        #[cfg(feature = "stats")]
+        stats::record_realloc();
+        self.cap = new_cap;
+        self.check_invariants();
+    }
+
+    // grows geometrically like `push` does, so a loop of `reserve(1)` and
+    // `push` is still amortized O(1)
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reserve(&mut self, additional: usize) {
+        self.check_invariants();
+        let required = self.len().saturating_add(additional);
+        if required <= self.capacity() {
+            return;
+        }
+        self.grow_exact(required.max(self.growth_factor().as_usize()));
+    }
+
+    // rounds the buffer's byte size up to a power of two, the size classes
+    // allocators hand out anyway, so the slack they'd round in becomes usable
+    // capacity instead of being wasted. The result is only exactly a power of
+    // two when `size_of::<T>()` is one, other sizes get as many elements as
+    // fit under it (`[u8; 3]` rounds 15 bytes to 16 and ends up with 5)
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reserve_size_class(&mut self, additional: usize) {
+        self.check_invariants();
+        let required = self.len().saturating_add(additional);
+        if required <= self.capacity() {
+            return;
+        }
+        let size = core::mem::size_of::<T>();
+        if Self::IS_ZST || required > Limit::MAX.as_usize() {
+            return self.grow_exact(required);
+        }
+        let new_cap = match required.checked_mul(size).and_then(usize::checked_next_power_of_two) {
+            Some(bytes) => (bytes / size).min(Limit::MAX.as_usize()),
+            None => required,
+        };
+        self.grow_exact(new_cap.max(required));
+    }
+
+    // never over-allocates, the capacity ends up exactly `len + additional`
+    #[cfg(not(no_global_oom_handling))]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.check_invariants();
+        let required = self.len().saturating_add(additional);
+        if required <= self.capacity() {
+            return;
+        }
+        self.grow_exact(required);
+    }
+
+    // `reserve` that reports overflow and allocation failure instead of
+    // panicking or aborting
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.check_invariants();
+        let required = self.len().saturating_add(additional);
+        if required <= self.capacity() {
+            return Ok(());
+        }
+        let doubled = self.capacity().saturating_mul(2).min(Limit::MAX.as_usize());
+        self.try_grow_exact(required.max(doubled))
+    }
+
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.check_invariants();
+        let required = self.len().saturating_add(additional);
+        if required <= self.capacity() {
+            return Ok(());
+        }
+        self.try_grow_exact(required)
+    }
+
+    // like `reserve` but in terms of the total, handing back the capacity it
+    // ended up with so callers know how much room they really have
+    #[cfg(not(no_global_oom_handling))]
+    pub fn ensure_capacity(&mut self, total: usize) -> usize {
+        self.reserve(total.saturating_sub(self.len()));
+        self.capacity()
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    // inserting at `len` skips the shift entirely and is exactly a `push`
+    pub fn insert(&mut self, index: usize, element: T) {
+        self.check_invariants();
+        let len = self.len();
+        if len < index {
+            assert_failed(index, len);
+        }
+        if index == len {
+            self.push(element);
+            return;
+        }
+
+        // space for the new element
+        if self.cap == self.len {
+            self.grow();
+        }
+
+        unsafe {
+            // through the raw buffer, the slice from `as_mut_ptr` stops at `len`
+            let insert_on = self.ptr.as_ptr().add(index);
+
+            ptr::copy(insert_on, insert_on.add(1), len - index);
+            // Write it in, overwriting the first copy of the `index`th
+            // element.
+            ptr::write(insert_on, element);
+
+            self.len = self.len.add(Limit::ONE);
+        }
+        self.check_invariants();
+    }
+
+    // `insert` that hands `(index, element)` back instead of panicking, when
+    // `index` is past `len` or there's no room left to grow into
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<&mut T, (usize, T)> {
+        self.check_invariants();
+        let len = self.len();
+        if len < index {
+            return Err((index, element));
+        }
+        if self.cap == self.len {
+            let doubled = self.capacity().saturating_mul(2).max(Self::FIRST_ALLOC_SIZE);
+            let new_cap = doubled.min(Limit::MAX.as_usize()).max(len + 1);
+            if self.try_grow_exact(new_cap).is_err() {
+                return Err((index, element));
+            }
+        }
+
+        unsafe {
+            let insert_on = self.ptr.as_ptr().add(index);
+            ptr::copy(insert_on, insert_on.add(1), len - index);
+            ptr::write(insert_on, element);
+            self.len = self.len.add(Limit::ONE);
+            self.check_invariants();
+            Ok(&mut *insert_on)
+        }
+    }
+
+    // the iterator's elements are written straight into a gap when its size is
+    // known up front, anything it yields past that is pushed and rotated in
+    #[cfg(not(no_global_oom_handling))]
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, mut index: usize, iter: I) {
+        self.check_invariants();
+        let len = self.len();
+        if len < index {
+            assert_failed(index, len);
+        }
+        let mut iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        if upper == Some(lower) {
+            let mut gap = Gap::open(self, index, lower);
+            for elem in iter.by_ref().take(lower) {
+                gap.fill(elem);
+            }
+            index = gap.filled;
+        }
+
+        let before = self.len();
+        for elem in iter {
+            self.push(elem);
+        }
+        let added = self.len() - before;
+        self[index..].rotate_right(added);
+        self.check_invariants();
+    }
+
+    // moves the array's elements onto the end without any clone bound
+    #[cfg(not(no_global_oom_handling))]
+    pub fn extend_one_array<const N: usize>(&mut self, array: [T; N]) {
+        self.check_invariants();
+        self.reserve(N);
+        let array = ManuallyDrop::new(array);
+        unsafe {
+            let len = self.len();
+            ptr::copy_nonoverlapping(array.as_ptr(), self.ptr.as_ptr().add(len), N);
+            self.set_len(len + N);
+        }
+    }
+
+    // moves every element of `other` onto the end of `self`, leaving `other`
+    // empty with its capacity untouched
+    #[cfg(not(no_global_oom_handling))]
+    pub fn append(&mut self, other: &mut Self) {
+        self.check_invariants();
+        other.check_invariants();
+        let count = other.len();
+        if count == 0 {
+            return;
+        }
+        // `self` and `other` are both `&mut`, so they can't share a buffer and
+        // the copy below can't overlap
+        self.reserve(count);
+        unsafe {
+            other.len = Limit::ZERO;
+            let dst = self.ptr.as_ptr().add(self.len());
+            ptr::copy_nonoverlapping(other.ptr.as_ptr(), dst, count);
+            self.len = Limit::from_usize(self.len() + count);
+        }
+        self.check_invariants();
+        other.check_invariants();
+    }
+
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, Limit> {
+        self.check_invariants();
+        let len = self.len();
+        let Range { start, end } = slice_range(range, len);
+        unsafe {
+            // for `..` this is `clear` up front, the tail is empty so nothing
+            // moves back on drop and a forgotten drain leaks every element
+            self.len = Limit::from_usize(start);
+            let drained = std::slice::from_raw_parts(self.ptr.as_ptr().add(start), end - start);
+            Drain {
+                iter: drained.iter(),
+                tail_start: end,
+                tail_len: len - end,
+                vec: NonNull::from(self),
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    /// Splits into two owned vecs at `mid`. The head keeps the original buffer
+    /// and the tail is copied into a freshly allocated one.
+    ///
+    /// Named so it doesn't shadow `[T]::split_at` for borrowed vecs.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn into_split_at(mut self, mid: usize) -> (Self, Self) {
+        self.check_invariants();
+        let len = self.len();
+        assert!(mid <= len, "mid > len");
+        let tail_len = len - mid;
+        let mut tail = Self::with_capacity(tail_len);
+        unsafe {
+            self.len = Limit::from_usize(mid);
+            ptr::copy_nonoverlapping(self.ptr.as_ptr().add(mid), tail.ptr.as_ptr(), tail_len);
+            tail.set_len(tail_len);
+        }
+        self.check_invariants();
+        (self, tail)
+    }
+
+    // Whichever side is larger keeps the original buffer and the smaller one
+    // gets a new allocation. With a small `at` the returned suffix takes over
+    // the buffer, shifted down to its front, and `self` gets a tight new one
+    // holding the prefix. That saves allocating for the suffix, not moving it.
+    #[cfg(not(no_global_oom_handling))]
+    #[track_caller]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        self.check_invariants();
+        let len = self.len();
+        assert!(at <= len, "`at` split index (is {at}) should be <= len (is {len})");
+        let tail_len = len - at;
+        if at < tail_len {
+            let mut head = Self::with_capacity(at);
+            unsafe {
+                let base = self.ptr.as_ptr();
+                ptr::copy_nonoverlapping(base, head.ptr.as_ptr(), at);
+                ptr::copy(base.add(at), base, tail_len);
+                self.len = Limit::from_usize(tail_len);
+                head.set_len(at);
+            }
+            let suffix = core::mem::replace(self, head);
+            suffix.check_invariants();
+            return suffix;
+        }
+        let mut tail = Self::with_capacity(tail_len);
+        unsafe {
+            self.len = Limit::from_usize(at);
+            ptr::copy_nonoverlapping(self.ptr.as_ptr().add(at), tail.ptr.as_ptr(), tail_len);
+            tail.set_len(tail_len);
+        }
+        self.check_invariants();
+        tail
+    }
+
+    // the elements as whole `[T; N]` chunks from the front, plus whatever is
+    // left over at the end
+    pub fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        const { assert!(N != 0, "chunk size must be non-zero") };
+        let chunks = self.len() / N;
+        let (head, rest) = self.split_at(chunks * N);
+        unsafe { (std::slice::from_raw_parts(head.as_ptr().cast(), chunks), rest) }
+    }
+
+    pub fn as_chunks_mut<const N: usize>(&mut self) -> (&mut [[T; N]], &mut [T]) {
+        const { assert!(N != 0, "chunk size must be non-zero") };
+        let chunks = self.len() / N;
+        let (head, rest) = self.split_at_mut(chunks * N);
+        unsafe { (std::slice::from_raw_parts_mut(head.as_mut_ptr().cast(), chunks), rest) }
+    }
+
+    // chunks counted from the back, the leftover is at the front
+    pub fn as_rchunks<const N: usize>(&self) -> (&[T], &[[T; N]]) {
+        const { assert!(N != 0, "chunk size must be non-zero") };
+        let chunks = self.len() / N;
+        let (rest, tail) = self.split_at(self.len() - chunks * N);
+        unsafe { (rest, std::slice::from_raw_parts(tail.as_ptr().cast(), chunks)) }
+    }
+
+    pub fn as_rchunks_mut<const N: usize>(&mut self) -> (&mut [T], &mut [[T; N]]) {
+        const { assert!(N != 0, "chunk size must be non-zero") };
+        let chunks = self.len() / N;
+        let split = self.len() - chunks * N;
+        let (rest, tail) = self.split_at_mut(split);
+        unsafe { (rest, std::slice::from_raw_parts_mut(tail.as_mut_ptr().cast(), chunks)) }
+    }
+
+    // lazily removes and yields the elements in `range` that `filter` returns
+    // true for, compacting the rest in place as it goes
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, Limit, F>
+    where
+        F: FnMut(&mut T) -> bool,
+        R: RangeBounds<usize>,
+    {
+        let old_len = self.len();
+        let Range { start, end } = slice_range(range, old_len);
+        self.len = Limit::ZERO;
+        ExtractIf {
+            vec: self,
+            idx: start,
+            end,
+            del: 0,
+            back_del: 0,
+            old_len,
+            pred: filter,
+        }
+    }
+
+    // the compaction methods (and the truncating ones) hand the vec back so
+    // they chain, `v.retain(..).dedup()`
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> &mut Self {
+        self.retain_indexed(|_, elem| f(elem))
+    }
+
+    // also gives memory back once less than a quarter of the capacity is left
+    // in use, for long lived vecs that get purged now and then
+    pub fn retain_and_shrink<F: FnMut(&T) -> bool>(&mut self, f: F) -> &mut Self {
+        self.retain(f);
+        if self.len() < self.capacity() / 4 {
+            self.shrink_to_fit();
+        }
+        self
+    }
+
+    // `f` also gets each element's index from before any removals
+    pub fn retain_indexed<F: FnMut(usize, &T) -> bool>(&mut self, mut f: F) -> &mut Self {
+        self.check_invariants();
+        let len = self.len();
+        let ptr = self.ptr.as_ptr();
+        let mut guard = CompactGuard { read: 0, write: 0, vec: &mut *self };
+        unsafe {
+            while guard.read < len {
+                let cur = ptr.add(guard.read);
+                if f(guard.read, &*cur) {
+                    if guard.read != guard.write {
+                        ptr::copy_nonoverlapping(cur, ptr.add(guard.write), 1);
+                    }
+                    guard.write += 1;
+                    guard.read += 1;
+                } else {
+                    // step past it first so a panicking drop doesn't drop it twice
+                    guard.read += 1;
+                    ptr::drop_in_place(cur);
+                }
+            }
+        }
+        drop(guard);
+        self
+    }
+
+    // like `retain`, but the rejected elements are moved into the returned
+    // vec (in order) instead of being dropped
+    #[cfg(not(no_global_oom_handling))]
+    pub fn retain_split<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> SmallerVec<T, Limit> {
+        self.check_invariants();
+        let len = self.len();
+        let ptr = self.ptr.as_ptr();
+        let mut rejected = SmallerVec::new();
+        let mut guard = CompactGuard { read: 0, write: 0, vec: self };
+        unsafe {
+            while guard.read < len {
+                let cur = ptr.add(guard.read);
+                if f(&*cur) {
+                    if guard.read != guard.write {
+                        ptr::copy_nonoverlapping(cur, ptr.add(guard.write), 1);
+                    }
+                    guard.write += 1;
+                    guard.read += 1;
+                } else {
+                    guard.read += 1;
+                    rejected.push(ptr::read(cur));
+                }
+            }
+        }
+        rejected
+    }
+
+    // `same_bucket` gets each element along with the last one kept before it,
+    // the same pairing `Vec::dedup_by` uses
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) -> &mut Self {
+        self.check_invariants();
+        let len = self.len();
+        if len <= 1 {
+            return self;
+        }
+        let ptr = self.ptr.as_ptr();
+        let mut guard = CompactGuard { read: 1, write: 1, vec: &mut *self };
+        unsafe {
+            while guard.read < len {
+                let read_ptr = ptr.add(guard.read);
+                let prev_ptr = ptr.add(guard.write - 1);
+                if same_bucket(&mut *read_ptr, &mut *prev_ptr) {
+                    // step past it first so a panicking drop doesn't drop it twice
+                    guard.read += 1;
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    ptr::copy(read_ptr, ptr.add(guard.write), 1);
+                    guard.write += 1;
+                    guard.read += 1;
+                }
+            }
+        }
+        drop(guard);
+        self
+    }
+
+    #[inline]
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) -> &mut Self {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    // the last element of each run of equal keys survives instead of the
+    // first, for merges where the latest entry wins
+    pub fn dedup_by_key_keep_last<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) -> &mut Self {
+        self.check_invariants();
+        let len = self.len();
+        if len <= 1 {
+            return self;
+        }
+        let ptr = self.ptr.as_ptr();
+        let mut guard = CompactGuard { read: 1, write: 1, vec: &mut *self };
+        unsafe {
+            while guard.read < len {
+                let read_ptr = ptr.add(guard.read);
+                let prev_ptr = ptr.add(guard.write - 1);
+                if key(&mut *read_ptr) == key(&mut *prev_ptr) {
+                    // the newer element takes over the kept slot before the
+                    // older one is dropped, so a panicking drop leaves every
+                    // slot the guard sees initialized exactly once
+                    let older = ptr::read(prev_ptr);
+                    ptr::copy(read_ptr, prev_ptr, 1);
+                    guard.read += 1;
+                    drop(older);
+                } else {
+                    ptr::copy(read_ptr, ptr.add(guard.write), 1);
+                    guard.write += 1;
+                    guard.read += 1;
+                }
+            }
+        }
+        drop(guard);
+        self
+    }
+
+    // `Extend::extend` under a name that can't be shadowed by another trait's
+    // `extend` in generic code, elements are moved in and never cloned
+    #[cfg(not(no_global_oom_handling))]
+    pub fn append_from_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        Extend::extend(self, iter)
+    }
+
+    // flattens exact size chunks with a single reserve. The inner iterators
+    // are gathered up first so their lengths can be summed before anything is
+    // reserved, which costs a buffer for the iterators but never a regrow
+    #[cfg(not(no_global_oom_handling))]
+    pub fn extend_all<I, J>(&mut self, iters: I)
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = T>,
+        J::IntoIter: ExactSizeIterator,
+    {
+        let iters: Vec<J::IntoIter> = iters.into_iter().map(IntoIterator::into_iter).collect();
+        let total = iters.iter().fold(0usize, |n, j| n.saturating_add(j.len()));
+        self.reserve(total);
+        for j in iters {
+            self.extend(j);
+        }
+    }
+
+    // like `append` but takes `other` by value and from any limit, its
+    // emptied buffer is freed on the way out
+    #[cfg(not(no_global_oom_handling))]
+    pub fn extend_move<L2: Int>(&mut self, mut other: SmallerVec<T, L2>) {
+        self.check_invariants();
+        other.check_invariants();
+        let count = other.len();
+        self.reserve(count);
+        unsafe {
+            other.set_len(0);
+            let len = self.len();
+            ptr::copy_nonoverlapping(other.ptr.as_ptr(), self.ptr.as_ptr().add(len), count);
+            self.set_len(len + count);
+        }
+    }
+
+    // same as `drain(range)` with the drain dropped straight away
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.check_invariants();
+        let len = self.len();
+        let Range { start, end } = slice_range(range, len);
+        unsafe {
+            // cut the vec off before the range so a panicking drop leaks the
+            // tail instead of exposing dropped elements
+            self.len = Limit::from_usize(start);
+            let base = self.ptr.as_ptr();
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base.add(start), end - start));
+            ptr::copy(base.add(end), base.add(start), len - end);
+            self.len = Limit::from_usize(len - (end - start));
+        }
+        self.check_invariants();
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) -> T {
+        match self.try_remove(index) {
+            Some(elem) => elem,
+            None => remove_failed(index, self.len()),
+        }
+    }
+
+    pub fn try_remove(&mut self, index: usize) -> Option<T> {
+        self.check_invariants();
+        // Note: `>=` because it's *not* valid to remove after everything
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        unsafe {
+            // len goes down before the element is read out and the tail moved,
+            // so at no point does the vec own a slot that's been moved from
+            self.len = Limit::from_usize(len - 1);
+            let p = self.ptr.as_ptr().add(index);
+            let result = ptr::read(p);
+            ptr::copy(p.add(1), p, len - index - 1);
+            self.check_invariants();
+            Some(result)
+        }
+    }
+
+    #[track_caller]
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        match self.try_swap_remove(index) {
+            Some(elem) => elem,
+            None => remove_failed(index, self.len()),
+        }
+    }
+
+    // O(1) removal that fills the hole with the last element
+    pub fn try_swap_remove(&mut self, index: usize) -> Option<T> {
+        self.check_invariants();
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        unsafe {
+            self.len = Limit::from_usize(len - 1);
+            let base = self.ptr.as_ptr();
+            let result = ptr::read(base.add(index));
+            ptr::copy(base.add(len - 1), base.add(index), 1);
+            self.check_invariants();
+            Some(result)
+        }
+    }
+}
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn swap_failed(a: usize, b: usize, len: usize) -> ! {
+    panic!("swap indices: {a} and {b} should both be < len: {len}");
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn remove_failed(index: usize, len: usize) -> ! {
+    panic!("removal index: {index} should be < len: {len}");
+}
+
+// resolves `range` against `len`, panicking the same way slice indexing does
+#[track_caller]
+fn slice_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice from after maximum usize")),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("attempted to index slice up to maximum usize")),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        panic!("slice index starts at {start} but ends at {end}");
+    }
+    if end > len {
+        panic!("range end index {end} out of range for slice of length {len}");
+    }
+    start..end
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn copy_within_failed(dest: usize, count: usize, len: usize) -> ! {
+    panic!("copy_within dest: {dest} plus {count} elements should be <= len: {len}");
+}
+
+#[cold]
+#[inline(never)]
+fn assert_failed(index: usize, len: usize) -> ! {
+    panic!("insertion index: {index} should be <= len: {len}");
+}
+
+impl<T: Clone, Limit: Int> SmallerVec<T, Limit> {
+    // borrowed data gets cloned, owned data keeps its buffer where it can
+    #[cfg(not(no_global_oom_handling))]
+    pub fn from_cow(cow: Cow<'_, [T]>) -> Self {
+        match cow {
+            Cow::Borrowed(slice) => slice.into(),
+            Cow::Owned(vec) => Self::from_vec(vec),
+        }
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    pub fn insert_slice(&mut self, index: usize, slice: &[T]) {
+        self.check_invariants();
+        let len = self.len();
+        if len < index {
+            assert_failed(index, len);
+        }
+        let mut gap = Gap::open(self, index, slice.len());
+        for elem in slice {
+            gap.fill(elem.clone());
+        }
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.check_invariants();
+        self.reserve(other.len());
+        let ptr = self.ptr.as_ptr();
+        for elem in other {
+            // `len` only covers finished clones, so if one panics the vec
+            // holds (and later drops) exactly the ones before it
+            unsafe { ptr::write(ptr.add(self.len()), elem.clone()) };
+            self.len = self.len.add(Limit::ONE);
+        }
+        self.check_invariants();
+    }
+}
+
+impl<T: Copy, Limit: Int> SmallerVec<T, Limit> {
+    // an exactly sized snapshot of `slice` with a single memcpy
+    #[cfg(not(no_global_oom_handling))]
+    pub fn from_slice_copy(slice: &[T]) -> Self {
+        if slice.len() > Limit::MAX.as_usize() {
+            capacity_overflow::<Limit>(slice.len())
+        }
+        let mut vec = Self::with_capacity(slice.len());
+        unsafe {
+            ptr::copy_nonoverlapping(slice.as_ptr(), vec.ptr.as_ptr(), slice.len());
+            vec.set_len(slice.len());
+        }
+        vec
+    }
+
+    // one reserve and one memcpy, the cheapest way to append plain data
+    #[cfg(not(no_global_oom_handling))]
+    pub fn append_slice_copy(&mut self, slice: &[T]) {
+        self.check_invariants();
+        self.reserve(slice.len());
+        unsafe {
+            let len = self.len();
+            ptr::copy_nonoverlapping(slice.as_ptr(), self.ptr.as_ptr().add(len), slice.len());
+            self.set_len(len + slice.len());
+        }
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    pub fn repeat(&self, n: usize) -> SmallerVec<T, Limit> {
+        let len = self.len();
+        let total = len.saturating_mul(n);
+        let mut out = SmallerVec::with_capacity(total);
+        if total == 0 {
+            return out;
+        }
+        unsafe {
+            let dst = out.ptr.as_ptr();
+            ptr::copy_nonoverlapping(self.ptr.as_ptr(), dst, len);
+            // keep doubling the filled prefix, then top up whatever's left
+            let mut filled = len;
+            while filled <= total / 2 {
+                ptr::copy_nonoverlapping(dst, dst.add(filled), filled);
+                filled *= 2;
+            }
+            ptr::copy_nonoverlapping(dst, dst.add(filled), total - filled);
+            out.set_len(total);
+        }
+        out
+    }
+
+    // overlap safe, `dest` is where the first element of `src` ends up
+    #[track_caller]
+    pub fn copy_within<R: RangeBounds<usize>>(&mut self, src: R, dest: usize) {
+        let len = self.len();
+        let Range { start, end } = slice_range(src, len);
+        let count = end - start;
+        if dest > len - count {
+            copy_within_failed(dest, count, len);
+        }
+        unsafe {
+            let base = self.ptr.as_ptr();
+            ptr::copy(base.add(start), base.add(dest), count);
+        }
+    }
+
+    // the slice version already turns into a memset for single byte values,
+    // without reading the bytes of `value` (which may be uninit)
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        <[T]>::fill(&mut self[..], value);
+    }
+}
+
+impl<T: PartialEq, Limit: Int> SmallerVec<T, Limit> {
+    // only neighbours are compared, so values that aren't equal to themselves
+    // (like NaN) are never removed
+    #[inline]
+    pub fn dedup(&mut self) -> &mut Self {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    // removes the first element equal to `item`, keeping the order of the rest
+    pub fn remove_item(&mut self, item: &T) -> Option<T> {
+        let index = self.iter().position(|elem| elem == item)?;
+        self.try_remove(index)
+    }
+
+    // O(1) after the search, the last element moves into the hole
+    pub fn swap_remove_item(&mut self, item: &T) -> Option<T> {
+        let index = self.iter().position(|elem| elem == item)?;
+        self.try_swap_remove(index)
+    }
+}
+
+impl<T: Default, Limit: Int> SmallerVec<T, Limit> {
+    #[cfg(not(no_global_oom_handling))]
+    pub fn resize_default(&mut self, new_len: usize) {
+        let len = self.len();
+        if new_len > len {
+            self.extend(std::iter::repeat_with(T::default).take(new_len - len));
+        } else {
+            self.truncate(new_len);
+        }
+    }
+
+    pub fn fill_default(&mut self) {
+        for elem in self.iter_mut() {
+            *elem = T::default();
+        }
+    }
+}
+
+// Shifts the tail up to leave `size` uninitialized slots at `index`. The vec's
+// len only covers the head while the gap is open, so a panic while filling
+// can't expose the uninitialized slots, and dropping the guard closes the gap
+// back up around however many slots got filled.
+struct Gap<'a, T, Limit: Int> {
+    vec: &'a mut SmallerVec<T, Limit>,
+    filled: usize,
+    end: usize,
+    tail: usize,
+}
+
+impl<'a, T, Limit: Int> Gap<'a, T, Limit> {
+    #[cfg(not(no_global_oom_handling))]
+    fn open(vec: &'a mut SmallerVec<T, Limit>, index: usize, size: usize) -> Self {
+        vec.reserve(size);
+        let tail = vec.len() - index;
+        unsafe {
+            let at = vec.ptr.as_ptr().add(index);
+            ptr::copy(at, at.add(size), tail);
+        }
+        vec.len = Limit::from_usize(index);
+        Gap {
+            vec,
+            filled: index,
+            end: index + size,
+            tail,
+        }
+    }
+
+    fn fill(&mut self, value: T) {
+        debug_assert!(self.filled < self.end);
+        unsafe {
+            ptr::write(self.vec.ptr.as_ptr().add(self.filled), value);
+        }
+        self.filled += 1;
+    }
+}
+
+impl<T, Limit: Int> Drop for Gap<'_, T, Limit> {
+    fn drop(&mut self) {
+        unsafe {
+            let base = self.vec.ptr.as_ptr();
+            if self.filled != self.end {
+                ptr::copy(base.add(self.end), base.add(self.filled), self.tail);
+            }
+            self.vec.len = Limit::from_usize(self.filled + self.tail);
+        }
+        self.vec.check_invariants();
+    }
+}
+
+// flattening for vecs of slices (or of other vecs) that sizes the output once
+#[cfg(not(no_global_oom_handling))]
+impl<V, Limit: Int> SmallerVec<V, Limit> {
+    pub fn concat<T: Clone>(&self) -> SmallerVec<T, Limit>
+    where
+        V: AsRef<[T]>,
+    {
+        let total = self.iter().map(|piece| piece.as_ref().len()).sum();
+        let mut out = SmallerVec::with_capacity(total);
+        for piece in self.iter() {
+            out.extend_from_slice(piece.as_ref());
+        }
+        out
+    }
+
+    pub fn join<T: Clone>(&self, sep: &T) -> SmallerVec<T, Limit>
+    where
+        V: AsRef<[T]>,
+    {
+        let seps = self.len().saturating_sub(1);
+        let total = self.iter().map(|piece| piece.as_ref().len()).sum::<usize>() + seps;
+        let mut out = SmallerVec::with_capacity(total);
+        for (i, piece) in self.iter().enumerate() {
+            if i != 0 {
+                out.push(sep.clone());
+            }
+            out.extend_from_slice(piece.as_ref());
+        }
+        out
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+// The up front reservation is clamped to what's left under `Limit::MAX`, so an
+// iterator with no upper bound (a bare `repeat(x)`) fills the vec to the limit
+// and then panics through `capacity_overflow()` rather than looping forever.
+impl<T, Limit: Int> Extend<T> for SmallerVec<T, Limit> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.check_invariants();
+        let mut iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        let room = Limit::MAX.as_usize() - self.len();
+        self.reserve(lower.min(room));
+        if upper == Some(lower) && lower <= room {
+            // exact size sources were just given all the room they need, so
+            // they're written straight in without the capacity check in `push`.
+            // This is what a `TrustedLen` specialization would buy (ranges,
+            // arrays, slice iters all report an exact hint) without nightly.
+            // `len` only ever moves past slots that were actually written, so
+            // a hint that claims too much just leaves spare capacity and one
+            // that claims too little falls through to the `push` loop
+            let ptr = self.ptr.as_ptr();
+            let mut len = self.len();
+            for elem in iter.by_ref().take(lower) {
+                unsafe { ptr::write(ptr.add(len), elem) };
+                len += 1;
+                self.len = Limit::from_usize(len);
+            }
+        }
+        for elem in iter {
+            // checked against `Limit` itself rather than left to `grow`, so an
+            // overrun is caught on the len before anything is written
+            if self.len == Limit::MAX {
+                capacity_overflow::<Limit>(self.len() + 1)
+            }
+            self.push(elem);
+        }
+        self.check_invariants();
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<'a, T: Copy + 'a, Limit: Int> Extend<&'a T> for SmallerVec<T, Limit> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, Limit: Int, const N: usize> Extend<[T; N]> for SmallerVec<T, Limit> {
+    fn extend<I: IntoIterator<Item = [T; N]>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0.saturating_mul(N));
+        for array in iter {
+            self.extend_one_array(array);
+        }
+    }
+}
 
-    const FIRST_ALLOC_SIZE: usize = match core::mem::size_of::<T>()  {
-        i if i == 1 => 8,
-        i if i <= 1024 => 4,
-        _ => 1,
-    };
+#[cfg(not(no_global_oom_handling))]
+impl<T, Limit: Int> FromIterator<T> for SmallerVec<T, Limit> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
 
-    pub const fn new() -> Self {
-        Self::new_unallocated()
+// `collect` can't tell what it's collecting from without specialization, so
+// buffer reuse for consumed vecs goes through `From` instead. This one leans
+// on std's in place `collect` into a `Vec`, then takes that buffer over when
+// its capacity fits in `Limit`.
+#[cfg(not(no_global_oom_handling))]
+impl<T, Limit: Int> From<std::vec::IntoIter<T>> for SmallerVec<T, Limit> {
+    fn from(iter: std::vec::IntoIter<T>) -> Self {
+        Self::from_vec(iter.collect())
     }
-    #[cfg(not(no_global_oom_handling))]
-    #[track_caller]
-    pub fn push(&mut self, value: T) {
-        if self.len == self.cap {
-            self.grow();
-        }
+}
 
+// adopts the iterator's buffer, sliding what's left of it down to the front
+#[cfg(not(no_global_oom_handling))]
+impl<T, Limit: Int> From<IntoIter<T>> for SmallerVec<T, Limit> {
+    fn from(iter: IntoIter<T>) -> Self {
+        if Self::IS_ZST || iter.cap > Limit::MAX.as_usize() {
+            return iter.collect();
+        }
+        let iter = ManuallyDrop::new(iter);
+        let len = iter.len();
         unsafe {
-            core::ptr::write(self.ptr.as_ptr().add(self.len.as_usize()), value);
+            ptr::copy(iter.start, iter.buf.as_ptr(), len);
+        }
+        SmallerVec {
+            ptr: iter.buf,
+            len: Limit::from_usize(len),
+            cap: Limit::from_usize(iter.cap),
         }
-
-        self.len = self.len.add(Limit::ONE);
     }
+}
 
-    #[cfg(not(no_global_oom_handling))]
-    pub fn pop(&mut self) -> Option<T> {
-        if self.len == Limit::ZERO {
-            None
-        } else {
-            self.len = self.len.sub(Limit::ONE);
-            unsafe { Some(core::ptr::read(self.ptr.as_ptr().add(self.len.as_usize()))) }
-        }
+#[cfg(not(no_global_oom_handling))]
+impl<'a, T: Copy + 'a, Limit: Int> FromIterator<&'a T> for SmallerVec<T, Limit> {
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
     }
+}
 
-    #[inline]
-    pub fn capacity(&self) -> usize {
-        self.cap.as_usize()
+#[cfg(not(no_global_oom_handling))]
+impl<T: Clone, Limit: Int> Clone for SmallerVec<T, Limit> {
+    fn clone(&self) -> Self {
+        let mut vec = Self::with_capacity(self.len());
+        vec.extend_from_slice(self);
+        vec
     }
 
-    #[inline]
-    pub fn len(&self) -> usize {
-        self.len.as_usize()
+    // reuses our buffer, only growing it (to exactly `source.len()`) if
+    // `source` doesn't fit
+    fn clone_from(&mut self, source: &Self) {
+        self.truncate(source.len());
+        let (init, tail) = source.split_at(self.len());
+        self.clone_from_slice(init);
+        self.reserve_exact(tail.len());
+        self.extend_from_slice(tail);
     }
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<Limit: Int> SmallerVec<u8, Limit> {
+    pub fn push_str(&mut self, s: &str) {
+        self.append_slice_copy(s.as_bytes());
     }
 
-    const fn new_unallocated() -> Self {
-        Self {
-            ptr: NonNull::dangling(),
-            len: Int::ZERO,
-            cap: Int::ZERO,
-        }
+    pub fn push_char(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
     }
+}
 
-    // This can't overflow an isize since self.cap.bits < isize::BITS.
-    #[cfg(not(no_global_oom_handling))]
-    #[inline(always)]
-    fn growth_factor(&self) -> Limit {
-        // self.cap.saturating_add(self.cap.sub(self.cap.half()))
-        self.cap.saturating_add(self.cap)
+impl<Limit: Int> SmallerVec<u8, Limit> {
+    pub fn find_byte(&self, needle: u8) -> Option<usize> {
+        self.iter().position(|&b| b == needle)
     }
-    #[cfg(not(no_global_oom_handling))]
-    fn grow(&mut self) {
-        // failout if allocation is already at the max
-        if self.cap == Limit::MAX {
-            capacity_overflow()
-        }
-        let (new_cap, new_layout) = if self.cap == Limit::ZERO {
-            (
-                Limit::from_usize(Self::FIRST_ALLOC_SIZE),
-                Layout::array::<T>(Self::FIRST_ALLOC_SIZE).unwrap(),
-            )
-        } else {
-            let new_cap = self.growth_factor();
-            // `Layout::array` checks that the number of bytes is <= usize::MAX,
-            // but this is redundant since old_layout.size() <= isize::MAX,
-            // so the `unwrap` should never fail.
-            let new_layout = Layout::array::<T>(new_cap.as_usize()).unwrap();
-            (new_cap, new_layout)
-        };
 
-        // since the limit must be < usize it is also < isize
+    // `[u8]::contains` already goes through std's word at a time memchr
+    pub fn contains_byte(&self, needle: u8) -> bool {
+        self.contains(&needle)
+    }
 
-        let new_ptr = if self.cap == Limit::ZERO {
-            unsafe { alloc::alloc(new_layout) }
-        } else {
-            let old_layout = Layout::array::<T>(self.cap.as_usize()).unwrap() ;
-            let old_ptr = self.ptr.as_ptr() as *mut u8;
-            unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) }
-        };
+    // non-ASCII bytes are left as they are
+    pub fn make_ascii_uppercase(&mut self) {
+        (**self).make_ascii_uppercase()
+    }
 
-        // If allocation fails, `new_ptr` will be null, in which case we abort.
-        self.ptr = match NonNull::new(new_ptr as *mut T) {
-            Some(p) => p,
-            None => alloc::handle_alloc_error(new_layout),
-        };
-        self.cap = new_cap;
+    pub fn make_ascii_lowercase(&mut self) {
+        (**self).make_ascii_lowercase()
     }
 
-    #[cfg(not(no_global_oom_handling))]
-    pub fn insert(&mut self, index: usize, element: T) {
-        // space for the new element
-        if self.cap == self.len {
-            self.grow();
-        }
+    pub fn eq_ignore_ascii_case(&self, other: &[u8]) -> bool {
+        (**self).eq_ignore_ascii_case(other)
+    }
 
-        let len = self.len();
-        if len < index {
-            assert_failed(index, len);
+    // one shrink and no copy on valid UTF-8, otherwise the bytes come back
+    // untouched along with where the encoding broke
+    pub fn into_boxed_str(self) -> Result<Box<str>, (Self, std::str::Utf8Error)> {
+        match std::str::from_utf8(&self) {
+            Ok(_) => Ok(unsafe { std::str::from_boxed_utf8_unchecked(self.into_boxed_slice()) }),
+            Err(e) => Err((self, e)),
         }
-        unsafe {
-            let insert_on = self.as_mut_ptr().add(index);
-
-            ptr::copy(insert_on, insert_on.add(1), len - index);
-            // Write it in, overwriting the first copy of the `index`th
-            // element.
-            ptr::write(insert_on, element);
+    }
+}
 
-            self.len = self.len.add(Limit::ONE);
-        }
+// writes stop short at `Limit::MAX` instead of panicking, once full `write`
+// returns `Ok(0)` so `write_all` fails cleanly with `WriteZero`
+#[cfg(not(no_global_oom_handling))]
+impl<Limit: Int> std::io::Write for SmallerVec<u8, Limit> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let room = Limit::MAX.as_usize() - self.len();
+        let count = buf.len().min(room);
+        self.append_slice_copy(&buf[..count]);
+        Ok(count)
     }
 
-    #[cfg(not(no_global_oom_handling))]
-    pub fn remove(&mut self, index: usize) -> T {
-        // Note: `<` because it's *not* valid to remove after everything
-        let len = self.len();
-        if len < index {
-            assert_failed(index, len);
-        }
-        unsafe {
-            self.len = self.len.sub(Limit::ONE);
-            let p = self.as_mut_ptr().add(index);
-            let result = ptr::read(p);
-            ptr::copy(p.add(index + 1), p, len - index);
-            result
-        }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
-#[cold]
-#[inline(never)]
-fn assert_failed(index: usize, len: usize) -> ! {
-    panic!("insertion index: {index} should be <= len: {len}");
+
+// Used by the in-place compaction methods (`retain`, `dedup` and friends).
+// Slots between `write` and `read` have been moved out of or dropped. Dropping
+// this (normally or on unwind) slides the unread elements down over them.
+struct CompactGuard<'a, T, Limit: Int> {
+    read: usize,
+    write: usize,
+    vec: &'a mut SmallerVec<T, Limit>,
 }
 
-impl<T: Clone, Limit: Int> SmallerVec<T, Limit> {
-    pub fn extend_from_slice(&mut self, other: &[T]) {
-        for elem in other {
-            self.push(elem.clone())
+impl<T, Limit: Int> Drop for CompactGuard<'_, T, Limit> {
+    fn drop(&mut self) {
+        unsafe {
+            let ptr = self.vec.ptr.as_ptr();
+            let unread = self.vec.len() - self.read;
+            ptr::copy(ptr.add(self.read), ptr.add(self.write), unread);
+            self.vec.len = Limit::from_usize(self.write + unread);
         }
+        self.vec.check_invariants();
     }
 }
 
@@ -210,16 +1798,33 @@ impl<T, Limit: Int> Default for SmallerVec<T, Limit> {
 }
 
 
+// `requested` is the element count that didn't fit in `Limit`
+#[cfg(not(no_global_oom_handling))]
+#[cold]
+#[track_caller]
+fn capacity_overflow<Limit: Int>(requested: usize) -> ! {
+    panic!(
+        "capacity overflow: requested {requested} elements, limit {} max {}",
+        core::any::type_name::<Limit>(),
+        Limit::MAX.as_usize()
+    );
+}
+
+// the count fit in `Limit`, it's the byte size that's past `isize::MAX`
 #[cfg(not(no_global_oom_handling))]
 #[cold]
-fn capacity_overflow() -> ! {
-    panic!("capacity overflow");
+#[track_caller]
+fn allocation_too_large<T>(requested: usize) -> ! {
+    panic!(
+        "allocation too large: requested {requested} elements of {} bytes each",
+        core::mem::size_of::<T>()
+    );
 }
 
 impl<T, Limit: Int> Drop for SmallerVec<T, Limit> {
     fn drop(&mut self) {
-        if self.cap != Limit::ZERO {
-            while self.pop().is_some() {}
+        self.clear();
+        if self.cap != Limit::ZERO && !Self::IS_ZST {
             let layout = Layout::array::<T>(self.cap.as_usize()).unwrap();
             unsafe {
                 alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
@@ -243,6 +1848,196 @@ impl<T, Limit: Int> std::ops::DerefMut for SmallerVec<T, Limit> {
     }
 }
 
+impl<T, Limit: Int> std::ops::Index<usize> for SmallerVec<T, Limit> {
+    type Output = T;
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        if index >= len {
+            index_failed::<Limit>(index, len);
+        }
+        unsafe { &*self.ptr.as_ptr().add(index) }
+    }
+}
+
+impl<T, Limit: Int> std::ops::IndexMut<usize> for SmallerVec<T, Limit> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        if index >= len {
+            index_failed::<Limit>(index, len);
+        }
+        unsafe { &mut *self.ptr.as_ptr().add(index) }
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn index_failed<Limit: Int>(index: usize, len: usize) -> ! {
+    panic!(
+        "index out of bounds: the len is {len} but the index is {index} (SmallerVec<_, {}>)",
+        core::any::type_name::<Limit>()
+    );
+}
+
+// ranges go through the slice so they index (and panic) exactly like `Vec`
+macro_rules! impl_range_index {
+    ($($range:ty),*) => {
+        $(
+            impl<T, Limit: Int> std::ops::Index<$range> for SmallerVec<T, Limit> {
+                type Output = [T];
+                #[inline]
+                #[track_caller]
+                fn index(&self, index: $range) -> &[T] {
+                    &(**self)[index]
+                }
+            }
+
+            impl<T, Limit: Int> std::ops::IndexMut<$range> for SmallerVec<T, Limit> {
+                #[inline]
+                #[track_caller]
+                fn index_mut(&mut self, index: $range) -> &mut [T] {
+                    &mut (**self)[index]
+                }
+            }
+        )*
+    };
+}
+
+impl_range_index!(
+    Range<usize>,
+    std::ops::RangeFrom<usize>,
+    std::ops::RangeTo<usize>,
+    std::ops::RangeFull,
+    std::ops::RangeInclusive<usize>,
+    std::ops::RangeToInclusive<usize>,
+    (Bound<usize>, Bound<usize>)
+);
+
+// the buffer comes from the global allocator with the same array layout `Vec`
+// uses, so it can be handed over as is
+impl<T, Limit: Int> From<SmallerVec<T, Limit>> for Vec<T> {
+    fn from(vec: SmallerVec<T, Limit>) -> Self {
+        let vec = ManuallyDrop::new(vec);
+        unsafe { Vec::from_raw_parts(vec.ptr.as_ptr(), vec.len(), vec.capacity()) }
+    }
+}
+
+// the buffer is handed over as is once the bytes check out as UTF-8
+impl<Limit: Int> TryFrom<SmallerVec<u8, Limit>> for String {
+    type Error = std::string::FromUtf8Error;
+
+    fn try_from(vec: SmallerVec<u8, Limit>) -> Result<Self, Self::Error> {
+        String::from_utf8(Vec::from(vec))
+    }
+}
+
+impl<T, Limit: Int> From<SmallerVec<T, Limit>> for std::collections::VecDeque<T> {
+    fn from(vec: SmallerVec<T, Limit>) -> Self {
+        Vec::from(vec).into()
+    }
+}
+
+impl<T, Limit: Int> From<SmallerVec<T, Limit>> for Box<[T]> {
+    fn from(vec: SmallerVec<T, Limit>) -> Self {
+        vec.into_boxed_slice()
+    }
+}
+
+impl<T, Limit: Int> From<SmallerVec<T, Limit>> for Arc<[T]> {
+    fn from(vec: SmallerVec<T, Limit>) -> Self {
+        vec.into_arc_slice()
+    }
+}
+
+impl<T: Clone, Limit: Int> From<SmallerVec<T, Limit>> for Cow<'static, [T]> {
+    fn from(vec: SmallerVec<T, Limit>) -> Self {
+        Cow::Owned(vec.into())
+    }
+}
+
+impl<'a, T: Clone, Limit: Int> From<&'a SmallerVec<T, Limit>> for Cow<'a, [T]> {
+    fn from(vec: &'a SmallerVec<T, Limit>) -> Self {
+        Cow::Borrowed(vec)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T: Clone, Limit: Int> From<&[T]> for SmallerVec<T, Limit> {
+    fn from(slice: &[T]) -> Self {
+        let mut vec = Self::with_capacity(slice.len());
+        vec.extend_from_slice(slice);
+        vec
+    }
+}
+
+// Both go through the slice impls, which hash bytes with a single
+// `Hasher::write` and compare byte-like elements with memcmp, so hashing also
+// matches the equivalent `[T]` (and any other `Limit`).
+impl<T: std::hash::Hash, Limit: Int> std::hash::Hash for SmallerVec<T, Limit> {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+// `Limit` is only how the counts are stored, so vecs compare by their elements
+// whatever their limits
+impl<T, U, Limit: Int, L2: Int> PartialEq<SmallerVec<U, L2>> for SmallerVec<T, Limit>
+where
+    T: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &SmallerVec<U, L2>) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq, Limit: Int> Eq for SmallerVec<T, Limit> {}
+
+impl<T: PartialOrd, Limit: Int, L2: Int> PartialOrd<SmallerVec<T, L2>> for SmallerVec<T, Limit> {
+    #[inline]
+    fn partial_cmp(&self, other: &SmallerVec<T, L2>) -> Option<std::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: Ord, Limit: Int> Ord for SmallerVec<T, Limit> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T, Limit: Int> AsRef<[T]> for SmallerVec<T, Limit> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, Limit: Int> AsMut<[T]> for SmallerVec<T, Limit> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+// sound because `Hash`, `Eq` and `Ord` all match the slice's, so sets and maps
+// keyed by vecs can be looked up with a plain `&[T]`
+impl<T, Limit: Int> std::borrow::Borrow<[T]> for SmallerVec<T, Limit> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, Limit: Int> std::borrow::BorrowMut<[T]> for SmallerVec<T, Limit> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
 pub struct IntoIter<T> {
     buf: NonNull<T>,
     cap: usize,
@@ -256,25 +2051,7 @@ impl<T, Limit: Int> IntoIterator for SmallerVec<T, Limit> {
     fn into_iter(self) -> IntoIter<T> {
         // Make sure not to drop Vec since that would free the buffer
         let vec = ManuallyDrop::new(self);
-
-        // Can't destructure Vec since it's Drop
-        let ptr = vec.ptr;
-        let cap = vec.cap;
-        let len = vec.len;
-
-        unsafe {
-            IntoIter {
-                buf: ptr,
-                cap: cap.as_usize(),
-                start: ptr.as_ptr(),
-                end: if cap == Limit::ZERO {
-                    // can't offset off this pointer, it's not allocated!
-                    ptr.as_ptr()
-                } else {
-                    ptr.as_ptr().add(len.as_usize())
-                },
-            }
-        }
+        unsafe { IntoIter::from_raw(vec.ptr, vec.cap.as_usize(), vec.len.as_usize()) }
     }
 }
 
@@ -283,6 +2060,9 @@ impl<T> Iterator for IntoIter<T> {
     fn next(&mut self) -> Option<T> {
         if self.start == self.end {
             None
+        } else if Self::IS_ZST {
+            self.end = self.end.wrapping_byte_sub(1);
+            unsafe { Some(ptr::read(NonNull::dangling().as_ptr())) }
         } else {
             unsafe {
                 let result = ptr::read(self.start);
@@ -293,14 +2073,127 @@ impl<T> Iterator for IntoIter<T> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = (self.end as usize - self.start as usize) / core::mem::size_of::<T>();
+        let len = if Self::IS_ZST {
+            self.end as usize - self.start as usize
+        } else {
+            (self.end as usize - self.start as usize) / core::mem::size_of::<T>()
+        };
         (len, Some(len))
     }
+
+    // the remaining elements are dropped in place by `Drop` instead of being
+    // read out one at a time
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<T> {
+        self.next_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        let skip = n.min(self.len());
+        let skipped = ptr::slice_from_raw_parts_mut(self.start as *mut T, skip);
+        unsafe {
+            // move past the skipped elements first so a panicking drop can't
+            // leave them reachable for a second drop
+            if Self::IS_ZST {
+                self.end = self.end.wrapping_byte_sub(skip);
+            } else {
+                self.start = self.start.add(skip);
+            }
+            ptr::drop_in_place(skipped);
+        }
+        self.next()
+    }
+}
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIter<T> {
+    const IS_ZST: bool = core::mem::size_of::<T>() == 0;
+
+    // Takes ownership of `len` initialized elements at the front of a buffer of
+    // `cap`. An unallocated buffer always has `len == 0` (or `T` is a ZST), so
+    // offsetting the dangling pointer by `len` is an offset by zero.
+    unsafe fn from_raw(buf: NonNull<T>, cap: usize, len: usize) -> Self {
+        let start = buf.as_ptr();
+        IntoIter {
+            buf,
+            cap,
+            start,
+            end: if Self::IS_ZST {
+                // ZSTs have nothing to offset over, so `end` counts the
+                // remaining elements in bytes past `start` instead
+                start.wrapping_byte_add(len)
+            } else {
+                start.add(len)
+            },
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.start, self.len()) }
+    }
+}
+
+impl<T> Default for IntoIter<T> {
+    fn default() -> Self {
+        unsafe { IntoIter::from_raw(NonNull::dangling(), 0, 0) }
+    }
+}
+
+impl<T: Clone> Clone for IntoIter<T> {
+    // only the elements that haven't been yielded yet are cloned, into a buffer
+    // sized to fit them exactly
+    fn clone(&self) -> Self {
+        let remaining = self.as_slice();
+        let mut new = IntoIter::<T>::default();
+        if remaining.is_empty() || Self::IS_ZST {
+            // ZSTs live in the count alone, so the clones are handed over to
+            // `end` and read back out of thin air like the originals
+            for elem in remaining {
+                core::mem::forget(elem.clone());
+                new.end = new.end.wrapping_byte_add(1);
+            }
+            return new;
+        }
+
+        let layout = Layout::array::<T>(remaining.len()).unwrap();
+        let ptr = unsafe { alloc::alloc(layout) } as *mut T;
+        new.buf = match NonNull::new(ptr) {
+            Some(p) => p,
+            None => alloc::handle_alloc_error(layout),
+        };
+        new.cap = remaining.len();
+        new.start = ptr;
+        new.end = ptr;
+
+        // `end` only moves past written elements, so if a clone panics the
+        // partially built iterator drops exactly what it holds
+        for elem in remaining {
+            unsafe {
+                ptr::write(new.end as *mut T, elem.clone());
+                new.end = new.end.add(1);
+            }
+        }
+        new
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
 }
+
 impl<T> DoubleEndedIterator for IntoIter<T> {
     fn next_back(&mut self) -> Option<T> {
         if self.start == self.end {
             None
+        } else if Self::IS_ZST {
+            self.end = self.end.wrapping_byte_sub(1);
+            unsafe { Some(ptr::read(NonNull::dangling().as_ptr())) }
         } else {
             unsafe {
                 self.end = self.end.offset(-1);
@@ -311,13 +2204,23 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
 }
 impl<T> Drop for IntoIter<T> {
     fn drop(&mut self) {
-        if self.cap != 0 {
-            // drop any remaining elements
-            for _ in &mut *self {}
-            let layout = Layout::array::<T>(self.cap).unwrap();
-            unsafe {
-                alloc::dealloc(self.buf.as_ptr() as *mut u8, layout);
+        // frees the buffer on the way out, even if one of the drops panics
+        struct DeallocGuard<'a, T>(&'a mut IntoIter<T>);
+
+        impl<T> Drop for DeallocGuard<'_, T> {
+            fn drop(&mut self) {
+                if self.0.cap != 0 && !IntoIter::<T>::IS_ZST {
+                    let layout = Layout::array::<T>(self.0.cap).unwrap();
+                    unsafe {
+                        alloc::dealloc(self.0.buf.as_ptr() as *mut u8, layout);
+                    }
+                }
             }
         }
+
+        let guard = DeallocGuard(self);
+        // drop whatever wasn't yielded in place rather than reading it out
+        let remaining = ptr::slice_from_raw_parts_mut(guard.0.start as *mut T, guard.0.len());
+        unsafe { ptr::drop_in_place(remaining) };
     }
 }
@@ -3,7 +3,9 @@ use std::{alloc::Layout, ptr::NonNull};
 
 use crate::int_trait::Int;
 
-// if this is used the size will go up to 24 unless the compiler gets smarter
+// if this is used the size will go up to 24 unless the compiler gets smarter,
+// so `SmallerVec` keeps its own ptr/len/cap and only the tests below build one
+#[cfg_attr(not(test), allow(dead_code))]
 pub(crate) struct RawVec<T, Limit: Int> {
     ptr: NonNull<T>,
     cap: Limit,
@@ -12,6 +14,7 @@ pub(crate) struct RawVec<T, Limit: Int> {
 unsafe impl<T: Send, Limit: Int> Send for RawVec<T, Limit> {}
 unsafe impl<T: Sync, Limit: Int> Sync for RawVec<T, Limit> {}
 
+#[cfg_attr(not(test), allow(dead_code))]
 impl<T, Limit: Int> RawVec<T, Limit> {
     pub(crate) const fn new() -> Self {
         assert!(std::mem::size_of::<T>() != 0, "TODO: implement ZST support");
@@ -23,7 +26,7 @@ impl<T, Limit: Int> RawVec<T, Limit> {
 
     fn grow(&mut self) {
         if self.cap == Limit::MAX {
-            crate::capacity_overflow()
+            crate::capacity_overflow::<Limit>(self.cap.as_usize() + 1)
         }
 
         // This can't overflow because we ensure self.cap <= isize::MAX.
@@ -32,7 +35,55 @@ impl<T, Limit: Int> RawVec<T, Limit> {
         } else {
             self.cap.saturating_mul(Limit::ONE.add(Limit::ONE))
         };
+        self.realloc_to(new_cap);
+    }
+
+    // makes room for `additional` more past `len`, at least doubling so a run
+    // of small reserves is still amortized O(1)
+    pub(crate) fn reserve(&mut self, len: Limit, additional: Limit) {
+        if self.cap.saturating_sub(len).as_usize() >= additional.as_usize() {
+            return;
+        }
+        let required = len.as_usize() + additional.as_usize();
+        if required > Limit::MAX.as_usize() {
+            crate::capacity_overflow::<Limit>(required)
+        }
+        let doubled = self.cap.saturating_mul(Limit::ONE.add(Limit::ONE));
+        self.realloc_to(doubled.max(Limit::from_usize(required)));
+    }
 
+    // reallocates down to `cap`, freeing the buffer entirely at zero. Asking
+    // for more than the current capacity does nothing
+    pub(crate) fn shrink_to(&mut self, cap: Limit) {
+        if cap.as_usize() >= self.cap.as_usize() {
+            return;
+        }
+        if cap == Limit::ZERO {
+            if let Some((ptr, layout)) = self.current_memory() {
+                unsafe { alloc::dealloc(ptr.as_ptr(), layout) };
+            }
+            self.ptr = NonNull::dangling();
+            self.cap = Limit::ZERO;
+            return;
+        }
+        self.realloc_to(cap);
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self, len: Limit) {
+        self.shrink_to(len);
+    }
+
+    // the block backing this vec, `None` while nothing is allocated
+    pub(crate) fn current_memory(&self) -> Option<(NonNull<u8>, Layout)> {
+        if self.cap == Limit::ZERO {
+            return None;
+        }
+        let layout = Layout::array::<T>(self.cap.as_usize()).unwrap();
+        Some((self.ptr.cast(), layout))
+    }
+
+    // moves the buffer to exactly `new_cap`, which must be nonzero
+    fn realloc_to(&mut self, new_cap: Limit) {
         // Layout::array checks that the number of bytes is <= usize::MAX,
         // but this is redundant since old_layout.size() <= isize::MAX,
         // so the `unwrap` should never fail.
@@ -44,12 +95,11 @@ impl<T, Limit: Int> RawVec<T, Limit> {
             "Allocation too large"
         );
 
-        let new_ptr = if self.cap == Limit::ZERO {
-            unsafe { alloc::alloc(new_layout) }
-        } else {
-            let old_layout = Layout::array::<T>(self.cap.as_usize()).unwrap();
-            let old_ptr = self.ptr.as_ptr() as *mut u8;
-            unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) }
+        let new_ptr = match self.current_memory() {
+            None => unsafe { alloc::alloc(new_layout) },
+            Some((old_ptr, old_layout)) => unsafe {
+                alloc::realloc(old_ptr.as_ptr(), old_layout, new_layout.size())
+            },
         };
 
         // If allocation fails, `new_ptr` will be null, in which case we abort.
@@ -63,11 +113,135 @@ impl<T, Limit: Int> RawVec<T, Limit> {
 
 impl<T, Limit: Int> Drop for RawVec<T, Limit> {
     fn drop(&mut self) {
-        if self.cap != Limit::ZERO {
-            let layout = Layout::array::<T>(self.cap.as_usize()).unwrap();
+        if let Some((ptr, layout)) = self.current_memory() {
             unsafe {
-                alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
+                alloc::dealloc(ptr.as_ptr(), layout);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RawVec;
+
+    // writes `0..len` through the raw pointer and reads it back
+    fn fill(raw: &mut RawVec<u32, u16>, len: u16) {
+        for i in 0..len {
+            unsafe { raw.ptr.as_ptr().add(i as usize).write(i as u32) };
+        }
+    }
+
+    fn holds(raw: &RawVec<u32, u16>, len: u16) -> bool {
+        (0..len).all(|i| unsafe { *raw.ptr.as_ptr().add(i as usize) } == i as u32)
+    }
+
+    #[test]
+    fn starts_unallocated() {
+        let raw = RawVec::<u32, u16>::new();
+        assert_eq!(raw.cap, 0);
+        assert!(raw.current_memory().is_none());
+    }
+
+    #[test]
+    fn grow_doubles_and_keeps_contents() {
+        use crate::common::allocations;
+
+        let mut raw = RawVec::<u32, u16>::new();
+        raw.grow();
+        assert_eq!(raw.cap, 1);
+        fill(&mut raw, 1);
+        // one realloc per doubling
+        let ((), n) = allocations(|| {
+            raw.grow();
+            raw.grow();
+        });
+        assert_eq!(n, 2);
+        assert_eq!(raw.cap, 4);
+        assert!(holds(&raw, 1));
+        let (ptr, layout) = raw.current_memory().unwrap();
+        assert_eq!(ptr.cast(), raw.ptr);
+        assert_eq!(layout.size(), 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow: requested 256 elements, limit u8 max 255")]
+    fn grow_past_the_limit() {
+        let mut raw = RawVec::<u8, u8>::new();
+        raw.reserve(0, 255);
+        raw.grow();
+    }
+
+    #[test]
+    fn reserve_is_amortized() {
+        let mut raw = RawVec::<u32, u16>::new();
+        raw.reserve(0, 3);
+        assert_eq!(raw.cap, 3);
+        fill(&mut raw, 3);
+        // already fits
+        raw.reserve(1, 2);
+        assert_eq!(raw.cap, 3);
+        // at least doubles
+        raw.reserve(3, 1);
+        assert_eq!(raw.cap, 6);
+        raw.reserve(3, 20);
+        assert_eq!(raw.cap, 23);
+        assert!(holds(&raw, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow: requested 300 elements, limit u8 max 255")]
+    fn reserve_past_the_limit() {
+        let mut raw = RawVec::<u8, u8>::new();
+        raw.reserve(0, 100);
+        raw.reserve(100, 200);
+    }
+
+    #[test]
+    fn shrink_keeps_contents() {
+        let mut raw = RawVec::<u32, u16>::new();
+        raw.reserve(0, 10);
+        fill(&mut raw, 4);
+        raw.shrink_to(20);
+        assert_eq!(raw.cap, 10);
+        raw.shrink_to(6);
+        assert_eq!(raw.cap, 6);
+        raw.shrink_to_fit(4);
+        assert_eq!(raw.cap, 4);
+        assert!(holds(&raw, 4));
+    }
+
+    #[test]
+    fn shrink_to_zero_frees() {
+        let mut raw = RawVec::<u32, u16>::new();
+        raw.reserve(0, 10);
+        raw.shrink_to_fit(0);
+        assert_eq!(raw.cap, 0);
+        assert!(raw.current_memory().is_none());
+        // and it can come back from that
+        raw.grow();
+        assert_eq!(raw.cap, 1);
+    }
+
+    #[test]
+    fn drop_frees_the_buffer() {
+        use crate::common::frees;
+        use std::rc::Rc;
+
+        // `RawVec` never drops elements, only the block they live in
+        let rc = Rc::new(());
+        let mut raw = RawVec::<Rc<()>, u8>::new();
+        raw.reserve(0, 4);
+        unsafe { raw.ptr.as_ptr().write(rc.clone()) };
+        let elem = unsafe { raw.ptr.as_ptr().read() };
+        let ((), freed) = frees(|| drop(raw));
+        assert_eq!(freed, 1);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(elem);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        // nothing to give back while unallocated
+        let ((), freed) = frees(|| drop(RawVec::<u32, u16>::new()));
+        assert_eq!(freed, 0);
+    }
+}
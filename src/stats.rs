@@ -0,0 +1,22 @@
+use std::cell::Cell;
+
+// kept per thread so concurrent work (or tests) on other threads doesn't skew it
+thread_local! {
+    static REALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// How many times vecs on the current thread have allocated, reallocated or
+/// shrunk their buffer. A vec's first allocation counts, as does freeing the
+/// buffer when shrinking to nothing; an attempt that fails doesn't.
+pub fn realloc_count() -> usize {
+    REALLOCS.with(Cell::get)
+}
+
+pub fn reset_realloc_count() {
+    REALLOCS.with(|count| count.set(0));
+}
+
+#[inline]
+pub(crate) fn record_realloc() {
+    REALLOCS.with(|count| count.set(count.get() + 1));
+}
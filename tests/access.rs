@@ -0,0 +1,108 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+fn get_in_bounds() {
+    let mut v: SmallerVec<u32, u8> = (0..4).collect();
+    assert_eq!(v.get(1), Some(&1));
+    assert_eq!(v.get(1..3), Some(&[1, 2][..]));
+    *v.get_mut(0).unwrap() = 7;
+    unsafe {
+        assert_eq!(*v.get_unchecked(0), 7);
+        *v.get_unchecked_mut(3) = 1;
+    }
+    assert_eq!(&*v, &[7, 1, 2, 1]);
+}
+
+#[test]
+fn get_out_of_bounds() {
+    let mut v: SmallerVec<u32, u8> = (0..4).collect();
+    assert_eq!(v.get(4), None);
+    assert_eq!(v.get(3..9), None);
+    assert!(v.get_mut(9).is_none());
+    assert!(v.get_mut(2..5).is_none());
+}
+
+#[test]
+fn first_and_last_empty() {
+    let mut v = SmallerVec::<u32, u8>::new();
+    assert!(v.first().is_none());
+    assert!(v.last().is_none());
+    assert!(v.first_mut().is_none());
+    assert!(v.last_mut().is_none());
+}
+
+#[test]
+fn first_and_last_mut_show_through_deref() {
+    let mut v: SmallerVec<u32, u8> = [1, 2, 3].into_iter().collect();
+    assert_eq!(v.first(), Some(&1));
+    assert_eq!(v.last(), Some(&3));
+    *v.last_mut().unwrap() = 9;
+    *v.first_mut().unwrap() = 0;
+    assert_eq!(&*v, &[0, 2, 9]);
+}
+
+#[test]
+fn swap() {
+    let mut v: SmallerVec<String, u8> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    v.swap(0, 2);
+    v.swap(1, 1);
+    assert_eq!(&*v, &["c", "b", "a"]);
+}
+
+#[test]
+#[should_panic(expected = "swap indices: 0 and 3 should both be < len: 3")]
+fn swap_out_of_bounds() {
+    let mut v: SmallerVec<u8, u8> = (0..3).collect();
+    v.swap(0, 3);
+}
+
+#[test]
+fn copy_within_forward() {
+    let mut v: SmallerVec<u8, u8> = (0..8).collect();
+    v.copy_within(0..3, 2);
+    assert_eq!(&*v, &[0, 1, 0, 1, 2, 5, 6, 7]);
+}
+
+#[test]
+fn copy_within_backward() {
+    let mut v: SmallerVec<u8, u8> = (0..8).collect();
+    v.copy_within(5.., 0);
+    assert_eq!(&*v, &[5, 6, 7, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn copy_within_onto_itself() {
+    let mut v: SmallerVec<u8, u8> = (0..8).collect();
+    v.copy_within(1..4, 1);
+    v.copy_within(.., 0);
+    assert_eq!(&*v, &[0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+#[should_panic(expected = "copy_within dest: 6")]
+fn copy_within_past_the_end() {
+    let mut v: SmallerVec<u8, u8> = (0..8).collect();
+    v.copy_within(0..3, 6);
+}
+
+#[test]
+fn as_chunks_and_as_rchunks() {
+    let mut v: SmallerVec<u8, u32> = (0..10).collect();
+    let (chunks, rest) = v.as_chunks::<4>();
+    assert_eq!(chunks, &[[0, 1, 2, 3], [4, 5, 6, 7]]);
+    assert_eq!(rest, &[8, 9]);
+    let (rest, chunks) = v.as_rchunks::<4>();
+    assert_eq!(rest, &[0, 1]);
+    assert_eq!(chunks, &[[2, 3, 4, 5], [6, 7, 8, 9]]);
+
+    let (chunks, rest) = v.as_chunks_mut::<4>();
+    chunks[1][0] = 40;
+    rest[0] = 80;
+    let (rest, chunks) = v.as_rchunks_mut::<4>();
+    rest[0] = 100;
+    chunks[0][0] = 20;
+    assert_eq!(&*v, &[100, 1, 20, 3, 40, 5, 6, 7, 80, 9]);
+
+    let empty: SmallerVec<u8, u8> = SmallerVec::new();
+    assert!(empty.as_chunks::<3>().0.is_empty());
+}
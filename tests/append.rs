@@ -0,0 +1,70 @@
+use smaller_vec::SmallerVec;
+use std::cell::Cell;
+
+thread_local!(static DROPS: Cell<u32> = const { Cell::new(0) });
+
+struct Counted(u32);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.with(|d| d.set(d.get() + 1));
+    }
+}
+
+#[test]
+fn large_append_moves_without_dropping() {
+    let mut a = SmallerVec::<Counted, u32>::new();
+    let mut b = SmallerVec::<Counted, u32>::new();
+    for i in 0..1000 {
+        a.push(Counted(i));
+        b.push(Counted(1000 + i));
+    }
+    let cap = b.capacity();
+    a.append(&mut b);
+    assert_eq!(DROPS.with(Cell::get), 0);
+    assert!(b.is_empty());
+    assert_eq!(b.capacity(), cap);
+    assert_eq!(a.len(), 2000);
+    assert!(a.iter().enumerate().all(|(i, c)| c.0 == i as u32));
+    drop(a);
+    drop(b);
+    assert_eq!(DROPS.with(Cell::get), 2000);
+}
+
+#[test]
+fn append_zsts() {
+    let mut a = SmallerVec::<(), u8>::new();
+    let mut b = SmallerVec::<(), u8>::new();
+    a.push(());
+    b.push(());
+    b.push(());
+    a.append(&mut b);
+    assert_eq!(a.len(), 3);
+    assert!(b.is_empty());
+}
+
+thread_local!(static CLONES: Cell<u32> = const { Cell::new(0) });
+
+struct CountsClones(u8);
+
+impl Clone for CountsClones {
+    fn clone(&self) -> Self {
+        CLONES.with(|c| c.set(c.get() + 1));
+        CountsClones(self.0)
+    }
+}
+
+#[test]
+fn append_from_iter_moves_the_values() {
+    let mut v: SmallerVec<String, u16> = SmallerVec::new();
+    let src = vec!["a".to_string(), "b".to_string()];
+    let heap = [src[0].as_ptr(), src[1].as_ptr()];
+    v.append_from_iter(src);
+    assert_eq!(&*v, &["a", "b"]);
+    assert_eq!([v[0].as_ptr(), v[1].as_ptr()], heap);
+
+    let mut w: SmallerVec<CountsClones, u8> = SmallerVec::new();
+    w.append_from_iter(vec![CountsClones(1), CountsClones(2)]);
+    assert_eq!(w[1].0, 2);
+    assert_eq!(CLONES.with(|c| c.get()), 0);
+}
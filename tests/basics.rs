@@ -0,0 +1,90 @@
+use smaller_vec::{SmallVec16, SmallVec8, SmallerVec};
+
+#[test]
+fn default_limit_and_aliases() {
+    let mut v = SmallerVec::<i32>::new();
+    v.push(1);
+    v.push(2);
+    assert_eq!(&*v, &[1, 2]);
+
+    let mut w: SmallVec16<u8> = SmallVec16::new();
+    w.push(3);
+    assert_eq!(&*w, &[3]);
+
+    let x: SmallVec8<u8> = Default::default();
+    assert!(x.is_empty());
+}
+
+// what C sees: `struct { T *ptr; Limit len; Limit cap; }`
+#[repr(C)]
+struct CVec<L> {
+    ptr: *mut u8,
+    len: L,
+    cap: L,
+}
+
+#[test]
+fn layout_matches_the_c_struct() {
+    use std::mem::{align_of, offset_of, size_of};
+
+    assert_eq!(size_of::<SmallerVec<u8, u8>>(), size_of::<CVec<u8>>());
+    assert_eq!(size_of::<SmallerVec<u8, u16>>(), size_of::<CVec<u16>>());
+    assert_eq!(size_of::<SmallerVec<u8, u32>>(), size_of::<CVec<u32>>());
+    assert_eq!(align_of::<SmallerVec<u8, u16>>(), align_of::<CVec<u16>>());
+    assert_eq!(offset_of!(CVec<u16>, len), size_of::<*mut u8>());
+    assert_eq!(offset_of!(CVec<u16>, cap), size_of::<*mut u8>() + 2);
+
+    let mut v = SmallerVec::<u8, u16>::new();
+    v.reserve(10);
+    v.push(7);
+    v.push(8);
+    let c = unsafe { &*(&v as *const SmallerVec<u8, u16> as *const CVec<u16>) };
+    assert_eq!(c.ptr as *const u8, v.as_ptr());
+    assert_eq!(c.len, 2);
+    assert_eq!(usize::from(c.cap), v.capacity());
+    assert_eq!(unsafe { *c.ptr.add(1) }, 8);
+}
+
+#[test]
+fn send_and_sync_follow_the_elements() {
+    fn send_sync<S: Send + Sync>() {}
+    send_sync::<SmallerVec<String, u8>>();
+    send_sync::<SmallerVec<Vec<u8>>>();
+
+    let v: SmallerVec<String, u8> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+    let v = std::thread::spawn(move || v).join().unwrap();
+    std::thread::scope(|s| {
+        s.spawn(|| assert_eq!(v.len(), 2));
+        s.spawn(|| assert_eq!(v[1], "b"));
+    });
+}
+
+#[test]
+fn len_and_capacity_in_const_context() {
+    static EMPTY: SmallerVec<u32, u16> = SmallerVec::new();
+    const LEN: usize = EMPTY.len();
+    const CAP: usize = EMPTY.capacity();
+    const IS_EMPTY: bool = EMPTY.is_empty();
+    assert_eq!(LEN, 0);
+    assert_eq!(CAP, 0);
+    const { assert!(IS_EMPTY) };
+}
+
+#[test]
+fn with_limit_infers_the_element() {
+    fn build(n: u8) -> SmallerVec<String, u16> {
+        let mut v = SmallerVec::with_limit::<u16>();
+        for i in 0..n {
+            v.push(i.to_string());
+        }
+        v
+    }
+    assert_eq!(&*build(3), &["0", "1", "2"]);
+
+    let mut v = SmallerVec::with_limit::<u8>();
+    v.push(1u64);
+    let _: &SmallVec8<u64> = &v;
+
+    static EMPTY: SmallVec8<u8> = SmallerVec::with_limit::<u8>();
+    assert!(EMPTY.is_empty());
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::allocations;
+use smaller_vec::{SmallerVec, SmallerVecBuilder};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn fill_to_capacity_without_allocating() {
+    let mut b: SmallerVecBuilder<u32, u16> = SmallerVecBuilder::new(5);
+    assert!(b.is_empty());
+    let (_, n) = allocations(|| {
+        for i in 0..5 {
+            b.push(i);
+        }
+    });
+    assert_eq!(n, 0);
+    assert_eq!(b.len(), 5);
+    assert_eq!(b.remaining(), 0);
+    assert_eq!(format!("{b:?}"), "SmallerVecBuilder([0, 1, 2, 3, 4])");
+    let v = b.finish();
+    assert_eq!(&*v, &[0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn overflow_hands_the_value_back() {
+    let mut b: SmallerVecBuilder<u32, u16> = SmallerVecBuilder::new(1);
+    assert_eq!(b.try_push(1), Ok(()));
+    assert_eq!(b.try_push(9), Err(9));
+    let r = catch_unwind(AssertUnwindSafe(|| b.push(7)));
+    let msg = *r.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(msg, "push past the reserved capacity of 1");
+    assert_eq!(&*b.finish(), &[1]);
+}
+
+#[test]
+fn push_within_capacity() {
+    let mut w: SmallerVec<u8, u8> = SmallerVec::new();
+    assert_eq!(w.push_within_capacity(1), Err(1));
+    w.reserve_exact(1);
+    assert_eq!(w.push_within_capacity(1), Ok(()));
+    assert_eq!(w.push_within_capacity(2), Err(2));
+    assert_eq!(&*w, &[1]);
+}
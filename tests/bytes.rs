@@ -0,0 +1,78 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+fn push_str_and_push_char_agree() {
+    let mut a: SmallerVec<u8, u16> = SmallerVec::new();
+    for c in "héllo".chars() {
+        a.push_char(c);
+    }
+    let mut b: SmallerVec<u8, u16> = SmallerVec::new();
+    b.push_str("hé");
+    b.push_str("llo");
+    assert_eq!(a, b);
+    assert_eq!(&*a, "héllo".as_bytes());
+    assert_eq!(String::try_from(a).unwrap(), "héllo");
+}
+
+#[test]
+fn invalid_utf8_into_string() {
+    let mut bad: SmallerVec<u8, u16> = SmallerVec::new();
+    bad.push_str("h");
+    bad.push(0xff);
+    assert!(String::try_from(bad).is_err());
+}
+
+#[test]
+fn find_byte_in_a_few_kb() {
+    let mut v: SmallerVec<u8, u32> = (0..8192u32).map(|i| (i % 200) as u8 + 10).collect();
+    let n = v.len();
+    v[0] = 1;
+    v[n - 1] = 2;
+    assert_eq!(v.find_byte(1), Some(0));
+    assert_eq!(v.find_byte(2), Some(n - 1));
+    assert_eq!(v.find_byte(3), None);
+    assert!(v.contains_byte(1));
+    assert!(v.contains_byte(2));
+    assert!(!v.contains_byte(3));
+    for b in [10u8, 100, 209] {
+        assert_eq!(v.find_byte(b), v.iter().position(|&x| x == b));
+    }
+}
+
+#[test]
+fn into_boxed_str_valid() {
+    let mut v: SmallerVec<u8, u16> = SmallerVec::with_capacity(64);
+    v.push_str("héllo");
+    assert_eq!(&*v.into_boxed_str().unwrap(), "héllo");
+    assert_eq!(&*SmallerVec::<u8, u8>::new().into_boxed_str().unwrap(), "");
+}
+
+#[test]
+fn into_boxed_str_invalid() {
+    let mut v: SmallerVec<u8, u16> = SmallerVec::new();
+    v.push_str("ab");
+    // first byte of a two byte sequence with nothing after it
+    v.push(0xC3);
+    let (bytes, e) = v.into_boxed_str().unwrap_err();
+    assert_eq!(&*bytes, &[b'a', b'b', 0xC3]);
+    assert_eq!(e.valid_up_to(), 2);
+}
+
+#[test]
+fn ascii_case_leaves_other_bytes_alone() {
+    let mut v: SmallerVec<u8, u8> = SmallerVec::new();
+    v.push_str("héllo");
+    v.make_ascii_uppercase();
+    assert_eq!(&*v, "HéLLO".as_bytes());
+    v.make_ascii_lowercase();
+    assert_eq!(&*v, "héllo".as_bytes());
+}
+
+#[test]
+fn eq_ignore_ascii_case_only_folds_ascii() {
+    let mut v: SmallerVec<u8, u8> = SmallerVec::new();
+    v.push_str("HéLLO");
+    assert!(v.eq_ignore_ascii_case("héllo".as_bytes()));
+    assert!(!v.eq_ignore_ascii_case("hÉllo".as_bytes()));
+    assert!(!v.eq_ignore_ascii_case(b"hello"));
+}
@@ -0,0 +1,47 @@
+use smaller_vec::SmallerVec;
+use std::rc::Rc;
+
+fn strings(n: u32, step: u32) -> SmallerVec<String, u8> {
+    (0..n).map(|i| (i * step).to_string()).collect()
+}
+
+// the old derived `Clone` copied the pointer, so both vecs freed one buffer
+#[test]
+fn clone_owns_its_own_buffer() {
+    let r = Rc::new(());
+    let a: SmallerVec<Rc<()>, u8> = (0..4).map(|_| r.clone()).collect();
+    let mut b = a.clone();
+    assert_ne!(a.as_ptr(), b.as_ptr());
+    assert_eq!(Rc::strong_count(&r), 9);
+    b.pop();
+    drop(a);
+    assert_eq!(Rc::strong_count(&r), 4);
+    drop(b);
+    assert_eq!(Rc::strong_count(&r), 1);
+}
+
+#[test]
+fn clone_from_keeps_the_capacity() {
+    let big = strings(50, 1);
+    let small = strings(3, 7);
+    let mut t = big.clone();
+    let cap = t.capacity();
+    let p = t.as_ptr();
+    t.clone_from(&small);
+    assert_eq!(&*t, &*small);
+    assert_eq!(t.capacity(), cap);
+    assert_eq!(t.as_ptr(), p);
+    t.clone_from(&big);
+    assert_eq!(&*t, &*big);
+}
+
+#[test]
+fn clone_from_grows_exactly() {
+    let source: SmallerVec<u8, u8> = (0..200).collect();
+    let mut t = SmallerVec::<u8, u8>::with_capacity(150);
+    t.extend(0..150);
+    // doubling 150 would be past the u8 limit
+    t.clone_from(&source);
+    assert_eq!(&*t, &*source);
+    assert_eq!(t.capacity(), 200);
+}
@@ -0,0 +1,52 @@
+// Counts allocations, reallocations and frees made on the current thread, so
+// tests running in parallel don't see each other's.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct Counting;
+
+thread_local!(static ALLOCS: Cell<usize> = const { Cell::new(0) });
+thread_local!(static FREES: Cell<usize> = const { Cell::new(0) });
+
+fn bump(counter: &'static std::thread::LocalKey<Cell<usize>>) {
+    // the thread local may already be gone while a thread shuts down
+    let _ = counter.try_with(|a| a.set(a.get() + 1));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        bump(&ALLOCS);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        bump(&FREES);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        bump(&ALLOCS);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+// runs `f` and returns its result along with how many allocations and
+// reallocations it made
+pub fn allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCS.with(Cell::get);
+    let r = f();
+    (r, ALLOCS.with(Cell::get) - before)
+}
+
+// runs `f` and returns its result along with how many blocks it freed. Not
+// every file that pulls this module in needs it
+#[allow(dead_code)]
+pub fn frees<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = FREES.with(Cell::get);
+    let r = f();
+    (r, FREES.with(Cell::get) - before)
+}
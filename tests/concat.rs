@@ -0,0 +1,46 @@
+use smaller_vec::SmallerVec;
+
+fn slices() -> SmallerVec<&'static [u8], u8> {
+    [&b"ab"[..], b"c", b"def"].into_iter().collect()
+}
+
+#[test]
+fn concat_three_slices() {
+    let c = slices().concat();
+    assert_eq!(&*c, b"abcdef");
+    assert_eq!(c.capacity(), 6);
+}
+
+#[test]
+fn join_with_separator() {
+    let j = slices().join(&b',');
+    assert_eq!(&*j, b"ab,c,def");
+    assert_eq!(j.capacity(), 8);
+}
+
+#[test]
+fn concat_nested_vecs() {
+    let mut n = SmallerVec::<SmallerVec<u32, u8>, u8>::new();
+    n.push([1, 2].into_iter().collect());
+    n.push(SmallerVec::new());
+    n.push([3].into_iter().collect());
+    assert_eq!(&*n.concat(), &[1, 2, 3]);
+    assert_eq!(&*n.join(&0), &[1, 2, 0, 0, 3]);
+}
+
+#[test]
+fn repeat_counts() {
+    let v: SmallerVec<u8, u16> = [1, 2, 3].into_iter().collect();
+    assert!(v.repeat(0).is_empty());
+    assert_eq!(&*v.repeat(1), &[1, 2, 3]);
+    let r = v.repeat(7);
+    assert_eq!(&*r, &*[1u8, 2, 3].repeat(7));
+    assert_eq!(r.capacity(), 21);
+}
+
+#[test]
+#[should_panic(expected = "requested 300 elements, limit u8 max 255")]
+fn repeat_past_limit() {
+    let v: SmallerVec<u8, u8> = [1, 2, 3].into_iter().collect();
+    v.repeat(100);
+}
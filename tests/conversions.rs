@@ -0,0 +1,178 @@
+use smaller_vec::SmallerVec;
+use std::collections::VecDeque;
+
+#[test]
+fn into_vec_deque_reuses_the_buffer() {
+    let v: SmallerVec<String, u8> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    let p = v.as_ptr();
+    let mut d = VecDeque::from(v);
+    assert_eq!(d.as_slices().0.as_ptr(), p);
+    d.push_front("z".into());
+    assert_eq!(d.pop_back().unwrap(), "c");
+    assert_eq!(d, ["z", "a", "b"]);
+}
+
+#[test]
+fn into_vec_deque_empty_and_zst() {
+    let d: VecDeque<u8> = SmallerVec::<u8, u8>::new().into();
+    assert!(d.is_empty());
+    let mut z = SmallerVec::<(), u8>::new();
+    z.push(());
+    let d: VecDeque<()> = z.into();
+    assert_eq!(d.len(), 1);
+}
+
+mod cow {
+    use smaller_vec::SmallerVec;
+    use std::borrow::Cow;
+
+    fn strings() -> SmallerVec<String, u8> {
+        ["a", "b"].iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn borrowed() {
+        let v = strings();
+        let b: Cow<[String]> = (&v).into();
+        assert!(matches!(b, Cow::Borrowed(s) if s.as_ptr() == v.as_ptr()));
+        let back = SmallerVec::<String, u8>::from_cow(b);
+        assert_eq!(back, v);
+        assert_ne!(back.as_ptr(), v.as_ptr());
+    }
+
+    #[test]
+    fn owned_keeps_the_buffer() {
+        let v = strings();
+        let p = v.as_ptr();
+        let o: Cow<[String]> = v.into();
+        assert!(matches!(&o, Cow::Owned(x) if x.as_ptr() == p));
+        let w = SmallerVec::<String, u8>::from_cow(o);
+        assert_eq!(w.as_ptr(), p);
+        assert_eq!(&*w, &["a", "b"]);
+    }
+
+    #[test]
+    fn owned_with_capacity_past_the_limit() {
+        let mut big: Vec<u8> = Vec::with_capacity(1000);
+        big.push(1);
+        let s = SmallerVec::<u8, u8>::from_cow(Cow::Owned(big));
+        assert_eq!(&*s, &[1]);
+        let z = SmallerVec::<(), u8>::from_cow(Cow::Owned(vec![(); 3]));
+        assert_eq!(z.len(), 3);
+    }
+}
+
+mod arc {
+    use smaller_vec::SmallerVec;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn strings() -> SmallerVec<String, u8> {
+        (0..4).map(|i| i.to_string()).collect()
+    }
+
+    fn read_on_another_thread(shared: Arc<[String]>) {
+        let theirs = Arc::clone(&shared);
+        let joined = thread::spawn(move || theirs.concat()).join().unwrap();
+        assert_eq!(joined, "0123");
+        assert_eq!(Arc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn into_arc_slice_shared_with_a_thread() {
+        let v = strings();
+        let heap: Vec<*const u8> = v.iter().map(|s| s.as_ptr()).collect();
+        let shared = v.into_arc_slice();
+        // moved in, not cloned
+        assert!(shared.iter().map(|s| s.as_ptr()).eq(heap));
+        read_on_another_thread(shared);
+    }
+
+    #[test]
+    fn into_boxed_then_arc_shared_with_a_thread() {
+        let mut v = strings();
+        v.reserve(100);
+        let heap: Vec<*const u8> = v.iter().map(|s| s.as_ptr()).collect();
+        let shared = v.into_boxed_then_arc();
+        assert!(shared.iter().map(|s| s.as_ptr()).eq(heap));
+        read_on_another_thread(shared);
+    }
+}
+
+#[test]
+fn into_parts_round_trip() {
+    let mut v: SmallerVec<String, u16> = (0..5).map(|i| i.to_string()).collect();
+    v.reserve(20);
+    let cap = v.capacity();
+    let snapshot = v.clone();
+    let p = v.as_ptr();
+    let (ptr, len, c) = v.into_parts();
+    assert_eq!(ptr.as_ptr() as *const String, p);
+    assert_eq!((len, c as usize), (5u16, cap));
+    let back = unsafe { SmallerVec::<String, u16>::from_parts(ptr, len, c) };
+    assert_eq!(back, snapshot);
+    assert_eq!(back.capacity(), cap);
+
+    let empty: SmallerVec<u8, u8> = SmallerVec::new();
+    let (ptr, len, c) = empty.into_parts();
+    let back = unsafe { SmallerVec::<u8, u8>::from_parts(ptr, len, c) };
+    assert!(back.is_empty());
+}
+
+#[test]
+fn from_slice_copy_snapshots_the_slice() {
+    let mut src: Vec<u32> = (0..50_000).collect();
+    let v = SmallerVec::<u32, u32>::from_slice_copy(&src);
+    src[0] = 99;
+    src.truncate(10);
+    assert_eq!(v.len(), 50_000);
+    assert_eq!(v.capacity(), 50_000);
+    assert_eq!(v[0], 0);
+    assert!(v.iter().copied().eq(0..50_000));
+    assert!(SmallerVec::<u8, u8>::from_slice_copy(&[]).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow: requested 300 elements, limit u8 max 255")]
+fn from_slice_copy_past_the_limit() {
+    SmallerVec::<u8, u8>::from_slice_copy(&[0; 300]);
+}
+
+#[test]
+fn into_reversed_keeps_the_buffer() {
+    let v: SmallerVec<u16, u8> = (0..9).collect();
+    let ptr = v.as_ptr();
+    let r = v.into_reversed();
+    assert_eq!(r.as_ptr(), ptr);
+    assert_eq!(&*r, &[8, 7, 6, 5, 4, 3, 2, 1, 0]);
+    assert!(SmallerVec::<u16, u8>::new().into_reversed().is_empty());
+}
+
+#[test]
+fn from_vec_into_iter_reuses_the_buffer() {
+    let v: Vec<String> = (0..50).map(|i| i.to_string()).collect();
+    let ptr = v.as_ptr();
+    let mut iter = v.into_iter();
+    iter.next();
+    let s: SmallerVec<String, u16> = SmallerVec::from(iter);
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(s.len(), 49);
+    assert_eq!(s[0], "1");
+}
+
+#[test]
+fn from_into_iter_reuses_the_buffer() {
+    let s: SmallerVec<String, u16> = (0..50).map(|i| i.to_string()).collect();
+    let (ptr, cap) = (s.as_ptr(), s.capacity());
+    let mut iter = s.into_iter();
+    iter.next();
+    iter.next_back();
+    let t: SmallerVec<String, u32> = SmallerVec::from(iter);
+    assert_eq!(t.as_ptr(), ptr);
+    assert_eq!(t.capacity(), cap);
+    assert_eq!(t.len(), 48);
+    assert_eq!((&*t[0], &*t[47]), ("1", "48"));
+
+    let empty: SmallerVec<u8, u8> = SmallerVec::from(SmallerVec::<u8, u8>::new().into_iter());
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,51 @@
+use smaller_vec::SmallerVec;
+use std::fmt::Debug;
+
+fn dedup_like_vec<T: PartialEq + Clone + Debug>(src: &[T]) {
+    let mut v: SmallerVec<T, u8> = src.iter().cloned().collect();
+    v.dedup();
+    let mut w = src.to_vec();
+    w.dedup();
+    // through Debug, since NaN never equals itself
+    assert_eq!(format!("{:?}", &*v), format!("{w:?}"));
+}
+
+#[test]
+fn dedup_nan_runs() {
+    dedup_like_vec(&[1.0, 1.0, f64::NAN, f64::NAN, 2.0, 2.0, 2.0, f64::NAN, 3.0]);
+}
+
+// equal when within one of each other, which isn't transitive. Like `Vec`,
+// each element is compared against the last one kept
+#[derive(Debug, Clone, Copy)]
+struct Near(i32);
+
+impl PartialEq for Near {
+    fn eq(&self, o: &Self) -> bool {
+        (self.0 - o.0).abs() <= 1
+    }
+}
+
+#[test]
+fn dedup_non_transitive_eq() {
+    dedup_like_vec(&[Near(0), Near(1), Near(2), Near(3), Near(5), Near(6), Near(8)]);
+}
+
+#[test]
+fn dedup_by_key() {
+    let mut s: SmallerVec<String, u8> = ["a", "A", "b", "B", "b", "c"].iter().map(|x| x.to_string()).collect();
+    s.dedup_by_key(|x| x.to_lowercase());
+    assert_eq!(&*s, &["a", "b", "c"]);
+}
+
+#[test]
+fn dedup_by_key_keep_last_keeps_the_newest_version() {
+    let mut v: SmallerVec<(u8, u32), u16> =
+        [(1, 1), (1, 2), (2, 1), (3, 1), (3, 2), (3, 3), (1, 3)].into_iter().collect();
+    v.dedup_by_key_keep_last(|&mut (key, _)| key);
+    assert_eq!(&*v, &[(1, 2), (2, 1), (3, 3), (1, 3)]);
+
+    let mut e: SmallerVec<u8, u8> = SmallerVec::new();
+    e.dedup_by_key_keep_last(|x| *x);
+    assert!(e.is_empty());
+}
@@ -0,0 +1,148 @@
+use smaller_vec::SmallerVec;
+use std::cell::Cell;
+
+thread_local!(static DROPS: Cell<u32> = const { Cell::new(0) });
+
+#[derive(Debug)]
+struct Counted(u32);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.with(|d| d.set(d.get() + 1));
+    }
+}
+
+fn counted(n: u32) -> SmallerVec<Counted, u16> {
+    let mut v = SmallerVec::new();
+    for i in 0..n {
+        v.push(Counted(i));
+    }
+    v
+}
+
+fn ids(v: &[Counted]) -> Vec<u32> {
+    v.iter().map(|c| c.0).collect()
+}
+
+#[test]
+fn alternating_front_and_back() {
+    let mut v = counted(10);
+    {
+        let mut d = v.drain(2..8);
+        assert_eq!(d.len(), 6);
+        assert_eq!(d.next().unwrap().0, 2);
+        assert_eq!(d.next_back().unwrap().0, 7);
+        assert_eq!(d.next().unwrap().0, 3);
+        assert_eq!(d.next_back().unwrap().0, 6);
+        assert_eq!(d.len(), 2);
+    }
+    assert_eq!(DROPS.with(Cell::get), 6);
+    assert_eq!(ids(&v), [0, 1, 8, 9]);
+    let back: Vec<u32> = v.drain(..).rev().map(|c| c.0).collect();
+    assert_eq!(back, [9, 8, 1, 0]);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn zst_drain_dropped_unconsumed() {
+    let mut z = SmallerVec::<(), u8>::new();
+    for _ in 0..5 {
+        z.push(());
+    }
+    let mut d = z.drain(1..4);
+    d.next_back();
+    drop(d);
+    assert_eq!(z.len(), 2);
+    drop(z.drain(..));
+    assert!(z.is_empty());
+}
+
+#[test]
+fn keep_rest_from_both_ends() {
+    let mut v: SmallerVec<u32, u16> = (0..6).collect();
+    let mut d = v.drain(1..5);
+    assert_eq!(d.next(), Some(1));
+    assert_eq!(d.next_back(), Some(4));
+    d.keep_rest();
+    assert_eq!(&*v, &[0, 2, 3, 5]);
+}
+
+#[test]
+fn keep_rest_owned_values() {
+    let mut s: SmallerVec<String, u8> = ["a", "b", "c", "d"].iter().map(|x| x.to_string()).collect();
+    let mut d = s.drain(..2);
+    assert_eq!(d.next().unwrap(), "a");
+    d.keep_rest();
+    assert_eq!(&*s, &["b", "c", "d"]);
+}
+
+#[test]
+fn keep_rest_zsts() {
+    let mut z = SmallerVec::<(), u8>::new();
+    for _ in 0..5 {
+        z.push(());
+    }
+    let mut d = z.drain(1..4);
+    d.next();
+    d.keep_rest();
+    assert_eq!(z.len(), 4);
+}
+
+mod full_range {
+    use smaller_vec::SmallerVec;
+    use std::rc::Rc;
+
+    fn shared(rc: &Rc<()>) -> SmallerVec<Rc<()>, u16> {
+        (0..10).map(|_| rc.clone()).collect()
+    }
+
+    #[test]
+    fn fully_consumed() {
+        let mut v: SmallerVec<String, u16> = (0..50).map(|i| i.to_string()).collect();
+        let cap = v.capacity();
+        let got: Vec<String> = v.drain(..).collect();
+        assert_eq!(got.len(), 50);
+        assert_eq!(got[49], "49");
+        assert!(v.is_empty());
+        assert_eq!(v.capacity(), cap);
+    }
+
+    #[test]
+    fn dropped_unconsumed() {
+        let rc = Rc::new(());
+        let mut v = shared(&rc);
+        drop(v.drain(..));
+        assert!(v.is_empty());
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn forgotten_leaks_but_leaves_the_vec_empty() {
+        let rc = Rc::new(());
+        let mut v = shared(&rc);
+        let mut d = v.drain(..);
+        d.next();
+        d.next_back();
+        std::mem::forget(d);
+        assert!(v.is_empty());
+        assert_eq!(Rc::strong_count(&rc), 9);
+        v.push(rc.clone());
+        drop(v);
+        assert_eq!(Rc::strong_count(&rc), 9);
+    }
+
+    #[test]
+    fn zsts() {
+        let mut z: SmallerVec<(), u8> = SmallerVec::new();
+        for _ in 0..5 {
+            z.push(());
+        }
+        assert_eq!(z.drain(..).count(), 5);
+        assert!(z.is_empty());
+        for _ in 0..5 {
+            z.push(());
+        }
+        drop(z.drain(..));
+        assert!(z.is_empty());
+    }
+}
@@ -0,0 +1,12 @@
+use smaller_vec::{CapacityError, SmallerVec};
+
+#[test]
+fn capacity_error_fields_and_display() {
+    let mut v = SmallerVec::<u8, u8>::with_capacity(4);
+    let e: CapacityError = unsafe { v.set_len_checked(9) }.unwrap_err();
+    assert_eq!(e.requested(), 9);
+    assert_eq!(e.max(), 4);
+    assert_eq!(e.to_string(), "length 9 exceeds the maximum of 4");
+    let boxed: Box<dyn std::error::Error> = Box::new(e.clone());
+    assert_eq!(boxed.to_string(), e.to_string());
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use common::allocations;
+use smaller_vec::SmallerVec;
+
+#[test]
+fn exact_size_collect_allocates_once() {
+    let (v, n) = allocations(|| (0u32..1000).collect::<SmallerVec<_, u16>>());
+    assert_eq!(n, 1);
+    assert!(v.iter().copied().eq(0..1000));
+
+    // no upper bound up front, so it has to grow as it goes
+    let (v, n) = allocations(|| (0u32..1000).filter(|_| true).collect::<SmallerVec<_, u16>>());
+    assert!(n > 1);
+    assert!(v.iter().copied().eq(0..1000));
+}
+
+#[test]
+fn exact_size_extend_allocates_once() {
+    let mut v = SmallerVec::<u32, u16>::new();
+    v.push(0);
+    let cap = v.capacity();
+    let (_, n) = allocations(|| v.extend(1..1000));
+    assert!(cap < 1000);
+    assert_eq!(n, 1);
+    assert!(v.iter().copied().eq(0..1000));
+}
+
+#[test]
+fn extend_with_string_arrays() {
+    let mut v = SmallerVec::<String, u16>::new();
+    v.extend((0..3).map(|i| [i.to_string(), (i * 10).to_string()]));
+    assert_eq!(&*v, &["0", "0", "1", "10", "2", "20"]);
+    v.extend_one_array([String::from("x"), String::from("y")]);
+    assert_eq!(&v[6..], &["x", "y"]);
+}
+
+#[test]
+fn collect_from_references() {
+    let v: SmallerVec<u8, u8> = [1u8, 2, 3].iter().collect();
+    assert_eq!(&*v, &[1, 2, 3]);
+    let w: SmallerVec<u8, u8> = v.iter().filter(|&&x| x > 1).collect();
+    assert_eq!(&*w, &[2, 3]);
+}
+
+#[test]
+fn extend_from_slice_panicking_clone() {
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    thread_local!(static DROPS: Cell<u32> = const { Cell::new(0) });
+    struct P(u32);
+    impl Clone for P {
+        fn clone(&self) -> Self {
+            if self.0 == 2 {
+                panic!("third clone");
+            }
+            P(self.0)
+        }
+    }
+    impl Drop for P {
+        fn drop(&mut self) {
+            DROPS.with(|d| d.set(d.get() + 1));
+        }
+    }
+
+    let src: Vec<P> = (0..5).map(P).collect();
+    let mut v = SmallerVec::<P, u8>::new();
+    let r = catch_unwind(AssertUnwindSafe(|| v.extend_from_slice(&src)));
+    assert!(r.is_err());
+    // the two finished clones are kept, nothing half built is dropped
+    assert_eq!(v.iter().map(|p| p.0).collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(DROPS.with(Cell::get), 0);
+    drop(v);
+    assert_eq!(DROPS.with(Cell::get), 2);
+    drop(src);
+    assert_eq!(DROPS.with(Cell::get), 7);
+}
+
+#[test]
+fn extend_move_across_limits_without_cloning() {
+    let a: SmallerVec<String, u8> = ["x", "y"].iter().map(|s| s.to_string()).collect();
+    let heap: Vec<*const u8> = a.iter().map(|s| s.as_ptr()).collect();
+    let mut b: SmallerVec<String, u32> = SmallerVec::new();
+    b.push("w".into());
+    b.extend_move(a);
+    assert_eq!(&*b, &["w", "x", "y"]);
+    // the same strings moved over, not copies of them
+    assert_eq!(b[1].as_ptr(), heap[0]);
+    assert_eq!(b[2].as_ptr(), heap[1]);
+}
+
+#[test]
+fn append_slice_copy_64k_in_one_allocation() {
+    let data: Vec<u8> = (0..65536u32).map(|x| (x * 7) as u8).collect();
+    let mut v = SmallerVec::<u8, u32>::new();
+    let (_, n) = allocations(|| v.append_slice_copy(&data));
+    assert_eq!(n, 1);
+    assert_eq!(&*v, &data[..]);
+    v.append_slice_copy(&[1, 2]);
+    assert_eq!(v.len(), 65538);
+    assert_eq!(&v[65536..], &[1, 2]);
+}
+
+// the point is the `Take<Repeat>` shape, `repeat_n` would be a different type
+#[allow(clippy::manual_repeat_n)]
+#[test]
+fn extend_clamps_an_oversized_hint() {
+    // `take` reports (100, Some(100)) even though `repeat` alone says unbounded
+    let mut v = SmallerVec::<u8, u8>::new();
+    let (_, n) = allocations(|| v.extend(std::iter::repeat(0u8).take(100)));
+    assert_eq!(n, 1);
+    assert_eq!(v.len(), 100);
+}
+
+#[test]
+#[should_panic(expected = "requested 256 elements, limit u8 max 255")]
+fn extend_unbounded_hits_the_limit() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    v.extend(std::iter::repeat(0u8));
+}
+
+#[test]
+fn collect_past_the_limit_panics_cleanly() {
+    use std::panic::catch_unwind;
+
+    let r = catch_unwind(|| (0..300u32).map(|i| i as u8).collect::<SmallerVec<u8, u8>>());
+    let msg = *r.unwrap_err().downcast::<String>().unwrap();
+    assert!(msg.contains("requested 256 elements, limit u8 max 255"), "{msg}");
+
+    let r = catch_unwind(|| (0..300u32).map(|i| i as u8).filter(|_| true).collect::<SmallerVec<u8, u8>>());
+    let msg = *r.unwrap_err().downcast::<String>().unwrap();
+    assert!(msg.contains("requested 256 elements, limit u8 max 255"), "{msg}");
+}
+
+#[test]
+fn extend_past_the_limit_keeps_what_fit() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut v: SmallerVec<Rc<()>, u8> = SmallerVec::new();
+    let r = catch_unwind(AssertUnwindSafe(|| v.extend(std::iter::repeat_with(|| rc.clone()).take(300))));
+    assert!(r.is_err());
+    assert_eq!(v.len(), 255);
+    drop(v);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn extend_all_flattens_in_order() {
+    let chunks: Vec<&[u8]> = vec![b"abc", b"", b"defgh", b"ij"];
+    let mut v = SmallerVec::<u8, u32>::new();
+    v.push(b'_');
+    v.extend_all(chunks.into_iter().map(|c| c.iter().copied()));
+    assert_eq!(&*v, b"_abcdefghij");
+
+    let mut s = SmallerVec::<String, u8>::new();
+    s.extend_all([vec!["a".to_string()], vec![], vec!["b".to_string(), "c".to_string()]]);
+    assert_eq!(&*s, &["a", "b", "c"]);
+}
+
+// yields `n` strings whatever its size_hint claims
+struct Liar(u32, (usize, Option<usize>));
+
+impl Iterator for Liar {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.0 == 0 {
+            return None;
+        }
+        self.0 -= 1;
+        Some(self.0.to_string())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.1
+    }
+}
+
+#[test]
+fn lying_size_hint_is_only_a_hint() {
+    for hint in [(100, Some(100)), (100, None), (0, Some(0)), (1, Some(1)), (usize::MAX, None)] {
+        let v: SmallerVec<String, u16> = Liar(5, hint).collect();
+        assert_eq!(v.len(), 5);
+        assert_eq!(v.last().unwrap(), "0");
+
+        let mut w: SmallerVec<String, u16> = (0..3).map(|i| i.to_string()).collect();
+        w.extend(Liar(4, hint));
+        assert_eq!(&*w, &["0", "1", "2", "3", "2", "1", "0"]);
+    }
+}
@@ -0,0 +1,92 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+fn keep_rest_after_two_matches() {
+    let mut v: SmallerVec<u32, u16> = (0..10).collect();
+    let mut it = v.extract_if(.., |x| *x % 2 == 0);
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next(), Some(2));
+    it.keep_rest();
+    // 4, 6 and 8 matched too but were never reached, so they stay in order
+    assert_eq!(&*v, &[1, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn extract_within_a_range() {
+    let mut v: SmallerVec<u32, u16> = (0..10).collect();
+    let taken: Vec<_> = v.extract_if(2..6, |x| *x > 3).collect();
+    assert_eq!(taken, [4, 5]);
+    assert_eq!(&*v, &[0, 1, 2, 3, 6, 7, 8, 9]);
+}
+
+fn strings(n: u32) -> SmallerVec<String, u16> {
+    (0..n).map(|i| i.to_string()).collect()
+}
+
+fn multiple_of_three(s: &str) -> bool {
+    s.parse::<u32>().unwrap() % 3 == 0
+}
+
+#[test]
+fn backward_matches_forward() {
+    let mut forward = strings(20);
+    let taken_forward: Vec<String> = forward.extract_if(2..17, |s| multiple_of_three(s)).collect();
+    let mut backward = strings(20);
+    let mut taken_backward: Vec<String> =
+        backward.extract_if(2..17, |s| multiple_of_three(s)).rev().collect();
+    taken_backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(taken_forward, taken_backward);
+    assert_eq!(taken_forward, ["3", "6", "9", "12", "15"]);
+}
+
+#[test]
+fn both_ends_dropped_early() {
+    let mut v = strings(20);
+    {
+        let mut it = v.extract_if(2..17, |s| multiple_of_three(s));
+        assert_eq!(it.next().unwrap(), "3");
+        assert_eq!(it.next_back().unwrap(), "15");
+        assert_eq!(it.next_back().unwrap(), "12");
+    }
+    let expected: Vec<String> = (0..20)
+        .filter(|i| ![3, 12, 15].contains(i))
+        .map(|i| i.to_string())
+        .collect();
+    assert_eq!(&*v, &expected[..]);
+}
+
+#[test]
+fn both_ends_until_they_meet() {
+    let mut v = strings(20);
+    let mut taken = Vec::new();
+    {
+        let mut it = v.extract_if(.., |s| multiple_of_three(s));
+        loop {
+            match (it.next(), it.next_back()) {
+                (None, None) => break,
+                (front, back) => taken.extend(front.into_iter().chain(back)),
+            }
+        }
+    }
+    assert_eq!(taken.len(), 7);
+    let survivors: Vec<String> = (0..20)
+        .filter(|i| i % 3 != 0)
+        .map(|i| i.to_string())
+        .collect();
+    assert_eq!(&*v, &survivors[..]);
+}
+
+#[test]
+fn panic_from_the_back_keeps_the_rest() {
+    let mut v = strings(10);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut it = v.extract_if(.., |s| {
+            assert_ne!(s, "5");
+            s.as_str() > "6"
+        });
+        while it.next_back().is_some() {}
+    }));
+    assert!(result.is_err());
+    assert_eq!(&*v, &["0", "1", "2", "3", "4", "5", "6"]);
+}
@@ -0,0 +1,97 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+#[should_panic(expected = "capacity overflow: requested 300 elements, limit u8 max 255")]
+fn overflow_names_the_limit() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    v.reserve(300);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow: requested 256 elements, limit u8 max 255")]
+fn push_past_limit_overflows() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    for i in 0..=255u32 {
+        v.push(i as u8);
+    }
+}
+
+#[test]
+fn ensure_capacity_returns_the_capacity() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    let cap = v.ensure_capacity(20);
+    assert_eq!(cap, v.capacity());
+    assert!(cap >= 20);
+    assert_eq!(v.ensure_capacity(3), cap);
+}
+
+#[test]
+#[should_panic(expected = "requested 256 elements, limit u8 max 255")]
+fn ensure_capacity_past_limit() {
+    SmallerVec::<u8, u8>::new().ensure_capacity(256);
+}
+
+#[test]
+fn capacity_in_bytes_empty() {
+    let v = SmallerVec::<u32, u16>::new();
+    assert_eq!(v.capacity_in_bytes(), 0);
+    assert_eq!(v.allocation_size(), 0);
+}
+
+#[test]
+fn capacity_in_bytes_scales_with_the_element() {
+    let v = SmallerVec::<u32, u16>::with_capacity(10);
+    assert_eq!(v.capacity_in_bytes(), 40);
+    assert_eq!(v.allocation_size(), 40);
+    let v = SmallerVec::<u16, u16>::with_capacity(10);
+    assert_eq!(v.capacity_in_bytes(), 20);
+    assert_eq!(v.allocation_size(), 20);
+}
+
+#[test]
+fn capacity_in_bytes_zst() {
+    let mut z = SmallerVec::<(), u8>::new();
+    z.push(());
+    assert_eq!(z.capacity_in_bytes(), 0);
+    assert_eq!(z.allocation_size(), 0);
+}
+
+#[test]
+fn reserve_exact_does_not_round_up() {
+    let mut v = SmallerVec::<u8, u32>::new();
+    v.reserve_exact(17);
+    assert_eq!(v.capacity(), 17);
+    v.push(1);
+    v.reserve_exact(20);
+    assert_eq!(v.capacity(), 21);
+}
+
+#[test]
+fn push_after_shrink_steps_back_up_to_the_floor() {
+    let mut v: SmallerVec<u8, u16> = SmallerVec::new();
+    v.push(1);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 1);
+    v.push(2);
+    assert_eq!(v.capacity(), 8);
+    assert_eq!(&*v, &[1, 2]);
+
+    // past the floor it just doubles
+    let mut w: SmallerVec<u32, u8> = (0..20).collect();
+    w.shrink_to_fit();
+    w.push(20);
+    assert_eq!(w.capacity(), 40);
+}
+
+#[test]
+fn push_after_truncate_reuses_the_capacity() {
+    let mut v: SmallerVec<u8, u16> = (0..8).collect();
+    let cap = v.capacity();
+    let p = v.as_ptr();
+    v.truncate(0);
+    for i in 0..8 {
+        v.push(i);
+    }
+    assert_eq!(v.capacity(), cap);
+    assert_eq!(v.as_ptr(), p);
+}
@@ -0,0 +1,37 @@
+use smaller_vec::SmallerVec;
+
+fn five() -> SmallerVec<u32, u16> {
+    (0..5).collect()
+}
+
+#[test]
+fn index_and_index_mut() {
+    let mut v = five();
+    v[1] = 9;
+    assert_eq!(v[1], 9);
+    assert_eq!(&v[1..3], &[9, 2]);
+    assert_eq!(&v[..], &[0, 9, 2, 3, 4]);
+    assert_eq!(&v[3..], &[3, 4]);
+    assert_eq!(&v[..=1], &[0, 9]);
+    v[2..4].fill(0);
+    assert_eq!(&*v, &[0, 9, 0, 0, 4]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 5 but the index is 7 (SmallerVec<_, u16>)")]
+fn index_out_of_bounds_names_the_limit() {
+    let _ = five()[7];
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 5 but the index is 5 (SmallerVec<_, u16>)")]
+fn index_mut_out_of_bounds_names_the_limit() {
+    five()[5] = 1;
+}
+
+#[test]
+#[should_panic(expected = "range end index 9 out of range for slice of length 5")]
+fn range_out_of_bounds() {
+    let v = five();
+    let _ = &v[2..9];
+}
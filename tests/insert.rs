@@ -0,0 +1,119 @@
+use smaller_vec::SmallerVec;
+use std::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+fn strings(range: std::ops::Range<u32>) -> SmallerVec<String, u8> {
+    let mut v = SmallerVec::new();
+    for i in range {
+        v.push(i.to_string());
+    }
+    v
+}
+
+#[test]
+fn insert_slice_matches_repeated_insert() {
+    let extra: Vec<String> = (10..14).map(|i| i.to_string()).collect();
+    for at in 0..=5 {
+        let mut a = strings(0..5);
+        let mut b = strings(0..5);
+        a.insert_slice(at, &extra);
+        for (k, x) in extra.iter().enumerate() {
+            b.insert(at + k, x.clone());
+        }
+        assert_eq!(&*a, &*b);
+    }
+}
+
+#[test]
+fn insert_many_matches_repeated_insert() {
+    let mut a = SmallerVec::<u32, u8>::new();
+    let mut b = SmallerVec::<u32, u8>::new();
+    for i in 0..5 {
+        a.push(i);
+        b.push(i);
+    }
+    a.insert_many(1, (0..20).filter(|x| x % 5 == 0));
+    for (k, x) in [0, 5, 10, 15].into_iter().enumerate() {
+        b.insert(1 + k, x);
+    }
+    assert_eq!(&*a, &*b);
+    assert_eq!(&*a, &[0, 0, 5, 10, 15, 1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn insert_slice_past_limit() {
+    let mut v = SmallerVec::<u32, u8>::new();
+    v.push(1);
+    v.insert_slice(0, &[0; 255]);
+}
+
+#[test]
+fn insert_slice_panicking_clone_closes_the_gap() {
+    struct P(u32, Rc<Cell<u32>>);
+    impl Clone for P {
+        fn clone(&self) -> Self {
+            if self.0 == 2 {
+                panic!("clone");
+            }
+            P(self.0, self.1.clone())
+        }
+    }
+    impl Drop for P {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    let src: Vec<P> = (0..4).map(|i| P(i, drops.clone())).collect();
+    let mut v = SmallerVec::<P, u8>::new();
+    v.push(P(9, drops.clone()));
+    v.push(P(10, drops.clone()));
+    let r = catch_unwind(AssertUnwindSafe(|| v.insert_slice(1, &src)));
+    assert!(r.is_err());
+    assert_eq!(v.iter().map(|p| p.0).collect::<Vec<_>>(), [9, 0, 1, 10]);
+    assert_eq!(drops.get(), 0);
+}
+
+#[test]
+fn insert_at_len_is_a_push() {
+    let mut a = SmallerVec::<u32, u16>::new();
+    let mut b = SmallerVec::<u32, u16>::new();
+    for i in 0..1000 {
+        a.insert(a.len(), i);
+        b.push(i);
+    }
+    assert_eq!(a, b);
+    assert_eq!(a.capacity(), b.capacity());
+    a.insert(0, 9);
+    assert_eq!(&a[..3], &[9, 0, 1]);
+}
+
+#[test]
+#[should_panic(expected = "insertion index: 4 should be <= len: 3")]
+fn insert_past_len() {
+    let mut v: SmallerVec<u32, u8> = (0..3).collect();
+    v.insert(4, 0);
+}
+
+#[test]
+fn try_insert_hands_out_the_slot() {
+    let mut v: SmallerVec<String, u8> = SmallerVec::new();
+    *v.try_insert(0, "b".into()).unwrap() += "!";
+    assert_eq!(v.try_insert(0, "a".into()).unwrap(), "a");
+    v.try_insert(2, "c".into()).unwrap();
+    assert_eq!(&*v, &["a", "b!", "c"]);
+}
+
+#[test]
+fn try_insert_hands_the_value_back() {
+    let mut v: SmallerVec<String, u8> = strings(0..3);
+    assert_eq!(v.try_insert(9, "z".into()), Err((9, "z".to_string())));
+    assert_eq!(v.len(), 3);
+
+    let mut full: SmallerVec<u8, u8> = (0..255).collect();
+    assert_eq!(full.try_insert(3, 7), Err((3, 7)));
+    assert_eq!(full.len(), 255);
+}
@@ -0,0 +1,186 @@
+use smaller_vec::{IntoIter, SmallerVec};
+use std::cell::Cell;
+
+fn strings(n: u32) -> SmallerVec<String, u8> {
+    let mut v = SmallerVec::new();
+    for i in 0..n {
+        v.push(i.to_string());
+    }
+    v
+}
+
+#[test]
+fn clone_of_partially_consumed_iter_is_independent() {
+    let mut it = strings(5).into_iter();
+    it.next();
+    let mut c = it.clone();
+    assert_eq!(format!("{c:?}"), r#"IntoIter(["1", "2", "3", "4"])"#);
+    assert_eq!(c.next().unwrap(), "1");
+    assert_eq!(c.next_back().unwrap(), "4");
+    assert_eq!(it.len(), 4);
+    assert_eq!(c.len(), 2);
+    assert_eq!(it.collect::<Vec<_>>(), ["1", "2", "3", "4"]);
+    assert_eq!(c.collect::<Vec<_>>(), ["2", "3"]);
+}
+
+#[test]
+fn default_is_empty() {
+    let mut it: IntoIter<String> = Default::default();
+    assert_eq!(it.len(), 0);
+    assert!(it.next().is_none());
+    assert!(it.next_back().is_none());
+}
+
+struct Counted<'a>(u32, &'a Cell<u32>);
+
+impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+        self.1.set(self.1.get() + 1);
+    }
+}
+
+fn six(drops: &Cell<u32>) -> SmallerVec<Counted<'_>, u8> {
+    let mut v = SmallerVec::new();
+    for i in 0..6 {
+        v.push(Counted(i, drops));
+    }
+    v
+}
+
+#[test]
+fn count_drops_everything_once() {
+    let drops = Cell::new(0);
+    assert_eq!(six(&drops).into_iter().count(), 6);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn last_drops_the_rest() {
+    let drops = Cell::new(0);
+    let last = six(&drops).into_iter().last().unwrap();
+    assert_eq!(last.0, 5);
+    assert_eq!(drops.get(), 5);
+    drop(last);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn nth_drops_the_skipped() {
+    let drops = Cell::new(0);
+    let mut it = six(&drops).into_iter();
+    let x = it.nth(2).unwrap();
+    assert_eq!(x.0, 2);
+    assert_eq!(drops.get(), 2);
+    assert!(it.nth(10).is_none());
+    assert_eq!(drops.get(), 5);
+    drop(x);
+    drop(it);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn drop_after_partial_use() {
+    let drops = Cell::new(0);
+    let mut it = six(&drops).into_iter();
+    it.next();
+    it.next_back();
+    assert_eq!(drops.get(), 2);
+    drop(it);
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn panicking_drop_still_drops_the_rest() {
+    struct Bomb<'a>(bool, &'a Cell<u32>);
+    impl Drop for Bomb<'_> {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+            if self.0 {
+                panic!("boom");
+            }
+        }
+    }
+    let drops = Cell::new(0);
+    let mut v: SmallerVec<Bomb, u8> = SmallerVec::new();
+    for i in 0..4 {
+        v.push(Bomb(i == 1, &drops));
+    }
+    let it = v.into_iter();
+    let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || drop(it)));
+    assert!(r.is_err());
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+fn zst_size_hint_counts_down() {
+    let mut v = SmallerVec::<(), u16>::new();
+    for _ in 0..5 {
+        v.push(());
+    }
+    assert_eq!(v.len(), 5);
+    let mut it = v.into_iter();
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    for left in (0..5).rev() {
+        it.next().unwrap();
+        assert_eq!(it.size_hint(), (left, Some(left)));
+    }
+    assert!(it.next().is_none());
+}
+
+#[test]
+fn zst_elements_are_dropped() {
+    thread_local!(static DROPS: Cell<u32> = const { Cell::new(0) });
+    #[derive(Clone)]
+    struct Z;
+    impl Drop for Z {
+        fn drop(&mut self) {
+            DROPS.with(|d| d.set(d.get() + 1));
+        }
+    }
+
+    let mut v = SmallerVec::<Z, u8>::new();
+    for _ in 0..7 {
+        v.push(Z);
+    }
+    let mut it = v.into_iter();
+    it.next();
+    it.next_back();
+    assert_eq!(DROPS.with(Cell::get), 2);
+    let c = it.clone();
+    assert_eq!(c.len(), 5);
+    drop(it);
+    drop(c);
+    assert_eq!(DROPS.with(Cell::get), 12);
+}
+
+#[test]
+fn into_iter_of_empty_vecs() {
+    let v: SmallerVec<String, u8> = SmallerVec::new();
+    assert_eq!(v.into_iter().next(), None);
+    let v: SmallerVec<String, u8> = SmallerVec::with_capacity(4);
+    let mut it = v.into_iter();
+    assert_eq!(it.len(), 0);
+    assert_eq!(it.next_back(), None);
+}
+
+#[test]
+fn into_iter_zst_len() {
+    let mut z: SmallerVec<(), u16> = SmallerVec::new();
+    for _ in 0..7 {
+        z.push(());
+    }
+    let mut it = z.into_iter();
+    assert_eq!(it.len(), 7);
+    assert_eq!(it.next_back(), Some(()));
+    assert_eq!(it.count(), 6);
+}
+
+#[test]
+fn into_iter_after_pops() {
+    let mut v = strings(5);
+    v.pop();
+    v.pop();
+    let it = v.into_iter();
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.collect::<Vec<_>>(), ["0", "1", "2"]);
+}
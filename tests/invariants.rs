@@ -0,0 +1,32 @@
+// the checks are `debug_assert!`s, so release builds have nothing to trip
+#![cfg(all(feature = "debug-invariants", debug_assertions))]
+
+use smaller_vec::SmallerVec;
+use std::mem::{self, ManuallyDrop};
+use std::ptr::NonNull;
+
+#[test]
+#[should_panic(expected = "len 9 is past cap 4")]
+fn corrupted_parts_are_caught() {
+    let (ptr, _, cap) = SmallerVec::<u8, u16>::with_capacity(4).into_parts();
+    let _ = unsafe { SmallerVec::<u8, u16>::from_parts(ptr, 9, cap) };
+}
+
+#[test]
+#[should_panic(expected = "zero sized elements with cap 3")]
+fn zst_parts_below_the_limit_are_caught() {
+    let _ = unsafe { SmallerVec::<(), u8>::from_parts(NonNull::dangling(), 0, 3) };
+}
+
+#[test]
+#[should_panic(expected = "len 9 is past cap 4")]
+fn len_corrupted_through_the_c_layout_is_caught_by_the_next_mutator() {
+    let mut v: ManuallyDrop<SmallerVec<u8, u16>> = ManuallyDrop::new(SmallerVec::with_capacity(4));
+    // `len` is the field right after the pointer, the way C code would see it
+    unsafe {
+        let base = &mut *v as *mut SmallerVec<u8, u16> as *mut u8;
+        base.add(mem::size_of::<NonNull<u8>>()).cast::<u16>().write(9);
+    }
+    assert_eq!(v.len(), 9);
+    v.push_within_capacity(1).unwrap();
+}
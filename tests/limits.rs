@@ -0,0 +1,56 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+fn widen_keeps_the_buffer() {
+    let mut v = SmallerVec::<u64, u8>::new();
+    for i in 0..100 {
+        v.push(i);
+    }
+    let p = v.as_ptr();
+    let w: SmallerVec<u64, u32> = v.widen_limit();
+    assert_eq!(w.as_ptr(), p);
+    assert_eq!(w.len(), 100);
+    assert!(w.iter().copied().eq(0..100));
+}
+
+#[test]
+fn shrink_limit_when_it_fits() {
+    let mut w = SmallerVec::<u64, u32>::new();
+    for i in 0..100 {
+        w.push(i);
+    }
+    let p = w.as_ptr();
+    let n: SmallerVec<u64, u8> = w.shrink_limit().unwrap();
+    assert_eq!(n.as_ptr(), p);
+    assert_eq!(n.len(), 100);
+}
+
+#[test]
+fn shrink_limit_past_the_limit() {
+    let mut big = SmallerVec::<u64, u32>::new();
+    for i in 0..300 {
+        big.push(i);
+    }
+    let mut big = big.shrink_limit::<u8>().unwrap_err();
+    assert_eq!(big.len(), 300);
+
+    // a capacity past the new limit is trimmed down to it
+    big.remove_range(200..);
+    let small = big.shrink_limit::<u8>().unwrap();
+    assert_eq!(small.capacity(), 255);
+    assert_eq!(small.len(), 200);
+    assert_eq!(small[199], 199);
+}
+
+#[test]
+fn len_as_u8_fits() {
+    let v: SmallerVec<u8, u32> = (0..255u32).map(|x| x as u8).collect();
+    assert_eq!(v.len_as::<u8>(), Some(255u8));
+}
+
+#[test]
+fn len_as_u8_overflows() {
+    let v: SmallerVec<u8, u32> = (0..256u32).map(|x| x as u8).collect();
+    assert_eq!(v.len_as::<u8>(), None);
+    assert_eq!(v.len_as::<u16>(), Some(256u16));
+}
@@ -0,0 +1,185 @@
+use smaller_vec::SmallerVec;
+use std::cell::Cell;
+
+// each element adds its own bit when dropped, so a double drop or a missed one
+// shows up in the total
+struct Bit<'a>(u32, &'a Cell<u32>);
+
+impl Drop for Bit<'_> {
+    fn drop(&mut self) {
+        let mask = 1 << self.0;
+        assert_eq!(self.1.get() & mask, 0, "{} dropped twice", self.0);
+        self.1.set(self.1.get() | mask);
+    }
+}
+
+fn bits(n: u32, dropped: &Cell<u32>) -> SmallerVec<Bit<'_>, u16> {
+    let mut v = SmallerVec::new();
+    for i in 0..n {
+        v.push(Bit(i, dropped));
+    }
+    v
+}
+
+fn ids(v: &[Bit]) -> Vec<u32> {
+    v.iter().map(|b| b.0).collect()
+}
+
+#[test]
+fn remove_range_drops_each_removed_once() {
+    let dropped = Cell::new(0);
+    let mut v = bits(8, &dropped);
+    v.remove_range(2..5);
+    assert_eq!(dropped.get(), 0b11100);
+    assert_eq!(ids(&v), [0, 1, 5, 6, 7]);
+    v.remove_range(3..);
+    v.remove_range(..=0);
+    assert_eq!(ids(&v), [1, 5]);
+    v.remove_range(..);
+    assert!(v.is_empty());
+    assert_eq!(dropped.get(), 0xff);
+}
+
+#[test]
+#[should_panic(expected = "range end index 9 out of range for slice of length 3")]
+fn remove_range_out_of_bounds() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    for _ in 0..3 {
+        v.push(1);
+    }
+    v.remove_range(1..9);
+}
+
+fn strings(n: u32) -> SmallerVec<String, u8> {
+    (0..n).map(|i| i.to_string()).collect()
+}
+
+// `remove` used to shift the tail down from `index * 2 + 1`, which scrambled
+// everything after a removal anywhere but the front
+#[test]
+fn remove_shifts_the_tail_down_by_one() {
+    for at in 0..6 {
+        let mut v = strings(6);
+        let mut expected: Vec<String> = v.to_vec();
+        assert_eq!(v.remove(at), expected.remove(at));
+        assert_eq!(&*v, &expected[..]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "removal index: 3 should be < len: 3")]
+fn remove_at_len_panics() {
+    let mut v: SmallerVec<u8, u8> = (0..3).collect();
+    v.remove(3);
+}
+
+#[test]
+fn try_remove() {
+    let mut v = strings(5);
+    assert_eq!(v.try_remove(1).unwrap(), "1");
+    assert_eq!(&*v, &["0", "2", "3", "4"]);
+    assert!(v.try_remove(4).is_none());
+    assert_eq!(v.len(), 4);
+}
+
+#[test]
+fn try_swap_remove() {
+    let mut v = strings(5);
+    assert_eq!(v.try_swap_remove(0).unwrap(), "0");
+    assert_eq!(&*v, &["4", "1", "2", "3"]);
+    assert_eq!(v.try_swap_remove(3).unwrap(), "3");
+    assert!(v.try_swap_remove(3).is_none());
+    assert_eq!(v.swap_remove(0), "4");
+    assert_eq!(&*v, &["2", "1"]);
+}
+
+#[test]
+fn pop_n() {
+    let mut v: SmallerVec<u32, u8> = (0..6).collect();
+    assert!(v.pop_n(0).is_empty());
+    assert_eq!(v.len(), 6);
+    assert_eq!(&*v.pop_n(2), &[4, 5]);
+    assert_eq!(&*v, &[0, 1, 2, 3]);
+    assert_eq!(&*v.pop_n(10), &[0, 1, 2, 3]);
+    assert!(v.is_empty());
+}
+
+mod front {
+    use smaller_vec::SmallerVec;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn pop_first_matches_vec_deque() {
+        let mut v: SmallerVec<String, u16> = (0..6).map(|i| i.to_string()).collect();
+        let mut d: VecDeque<String> = (0..6).map(|i| i.to_string()).collect();
+        loop {
+            let (a, b) = (v.pop_first(), d.pop_front());
+            assert_eq!(a, b);
+            assert!(v.iter().eq(d.iter()));
+            if a.is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn into_first_and_last_rest_match_vec_deque() {
+        let v: SmallerVec<u8, u8> = [1, 2, 3].into_iter().collect();
+        let mut d: VecDeque<u8> = [1, 2, 3].into_iter().collect();
+
+        let (first, rest) = v.clone().into_first_rest().unwrap();
+        assert_eq!(Some(first), d.pop_front());
+        assert!(rest.iter().eq(d.iter()));
+
+        let mut d: VecDeque<u8> = [1, 2, 3].into_iter().collect();
+        let (last, rest) = v.into_last_rest().unwrap();
+        assert_eq!(Some(last), d.pop_back());
+        assert!(rest.iter().eq(d.iter()));
+
+        assert!(SmallerVec::<u8, u8>::new().into_first_rest().is_none());
+        assert!(SmallerVec::<u8, u8>::new().into_last_rest().is_none());
+    }
+}
+
+// asserts in `Drop` that it only ever runs once per value
+struct Once(Cell<bool>);
+
+impl Drop for Once {
+    fn drop(&mut self) {
+        assert!(!self.0.replace(true), "dropped twice");
+    }
+}
+
+#[test]
+fn removed_values_drop_exactly_once() {
+    let drops = Cell::new(0);
+    {
+        let mut v: SmallerVec<(Once, Bit), u16> =
+            (0..10).map(|i| (Once(Cell::new(false)), Bit(i, &drops))).collect();
+        drop(v.remove(3));
+        drop(v.remove(0));
+        drop(v.swap_remove(0));
+        v.remove_range(1..3);
+        drop(v.remove(v.len() - 1));
+        assert_eq!(v.len(), 4);
+    }
+    assert_eq!(drops.get(), 0x3ff);
+}
+
+#[test]
+fn remove_item_present_and_absent() {
+    let mut v: SmallerVec<u8, u8> = [1, 2, 3, 2, 4].into_iter().collect();
+    assert_eq!(v.remove_item(&2), Some(2));
+    assert_eq!(&*v, &[1, 3, 2, 4]);
+    assert_eq!(v.remove_item(&9), None);
+    assert_eq!(&*v, &[1, 3, 2, 4]);
+}
+
+#[test]
+fn swap_remove_item_present_and_absent() {
+    let mut v: SmallerVec<u8, u8> = [1, 3, 2, 4].into_iter().collect();
+    assert_eq!(v.swap_remove_item(&1), Some(1));
+    assert_eq!(&*v, &[4, 3, 2]);
+    assert_eq!(v.swap_remove_item(&9), None);
+    assert_eq!(&*v, &[4, 3, 2]);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::allocations;
+use smaller_vec::SmallerVec;
+
+#[test]
+fn reserve_twice_allocates_once() {
+    let mut v: SmallerVec<u64, u32> = SmallerVec::new();
+    let (_, n) = allocations(|| {
+        v.reserve(10);
+        v.reserve(10);
+    });
+    assert_eq!(n, 1);
+    let (_, n) = allocations(|| {
+        v.reserve_exact(10);
+        assert!(v.try_reserve(10).is_ok());
+        assert!(v.try_reserve_exact(3).is_ok());
+    });
+    assert_eq!(n, 0);
+}
+
+#[test]
+fn try_reserve_past_the_limit_leaves_the_vec_alone() {
+    let mut w: SmallerVec<u8, u8> = SmallerVec::new();
+    let e = w.try_reserve(300).unwrap_err();
+    assert!(e.to_string().contains("300"), "{e}");
+    assert_eq!(w.capacity(), 0);
+    assert!(w.try_reserve(255).is_ok());
+    assert_eq!(w.capacity(), 255);
+}
+
+#[test]
+fn reserve_size_class_rounds_bytes_to_a_power_of_two() {
+    let mut v: SmallerVec<u32, u32> = SmallerVec::new();
+    v.push(1);
+    // 101 * 4 = 404 bytes, rounded up to 512
+    v.reserve_size_class(100);
+    assert_eq!(v.capacity_in_bytes(), 512);
+    assert_eq!(v.capacity(), 128);
+    assert!(v.capacity_in_bytes().is_power_of_two());
+    // already fits
+    v.reserve_size_class(100);
+    assert_eq!(v.capacity(), 128);
+}
+
+#[test]
+fn reserve_size_class_odd_sizes_and_limits() {
+    // 15 bytes round to 16, which only fits 5 three byte elements
+    let mut odd: SmallerVec<[u8; 3], u16> = SmallerVec::new();
+    odd.reserve_size_class(5);
+    assert_eq!(odd.capacity(), 5);
+    // 256 bytes would be past the limit
+    let mut small: SmallerVec<u8, u8> = SmallerVec::new();
+    small.reserve_size_class(200);
+    assert_eq!(small.capacity(), 255);
+}
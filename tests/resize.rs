@@ -0,0 +1,45 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+fn resize_default_grows_and_shrinks() {
+    let mut v: SmallerVec<Option<u32>, u16> = [Some(1), Some(2)].into_iter().collect();
+    v.resize_default(5);
+    assert_eq!(&*v, &[Some(1), Some(2), None, None, None]);
+    v.resize_default(1);
+    assert_eq!(&*v, &[Some(1)]);
+}
+
+#[test]
+fn fill_default_resets_in_place() {
+    let mut v: SmallerVec<Option<u32>, u16> = (0..4).map(Some).collect();
+    let len = v.len();
+    v.fill_default();
+    assert_eq!(v.len(), len);
+    assert!(v.iter().all(Option::is_none));
+}
+
+#[test]
+fn fill_bytes() {
+    let mut v: SmallerVec<u8, u16> = (0..100).collect();
+    v.fill(0);
+    assert!(v.iter().all(|&b| b == 0));
+    v.fill(0xAB);
+    assert!(v.iter().all(|&b| b == 0xAB));
+    assert_eq!(v.len(), 100);
+}
+
+#[test]
+fn fill_wider_values() {
+    let mut v: SmallerVec<(u8, u32), u8> = (0..10).map(|i| (i, i as u32)).collect();
+    v.fill((1, 0xABCD));
+    assert!(v.iter().all(|&x| x == (1, 0xABCD)));
+}
+
+#[test]
+fn fill_uninit_bytes() {
+    use std::mem::MaybeUninit;
+
+    let mut v: SmallerVec<MaybeUninit<u8>, u8> = (0..10).map(MaybeUninit::new).collect();
+    v.fill(MaybeUninit::uninit());
+    assert_eq!(v.len(), 10);
+}
@@ -0,0 +1,66 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+fn retain_indexed_keeps_even_indices() {
+    let src: Vec<String> = (0..9).map(|i| (i * 3).to_string()).collect();
+    let mut v: SmallerVec<String, u8> = SmallerVec::from(&src[..]);
+    let mut seen = vec![];
+    v.retain_indexed(|i, _| {
+        seen.push(i);
+        i % 2 == 0
+    });
+    // indices are the original positions, not shifted by earlier removals
+    assert_eq!(seen, (0..9).collect::<Vec<_>>());
+    assert_eq!(&*v, &["0", "6", "12", "18", "24"]);
+}
+
+#[test]
+fn retain_split_evens_and_odds() {
+    let mut v: SmallerVec<i32, u16> = (0..10).collect();
+    let odd = v.retain_split(|x| x % 2 == 0);
+    assert_eq!(&*v, &[0, 2, 4, 6, 8]);
+    assert_eq!(&*odd, &[1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn retain_split_moves_rather_than_clones() {
+    let mut s: SmallerVec<String, u8> = ["a", "bb", "c", "dd"].iter().map(|x| x.to_string()).collect();
+    let heap: Vec<*const u8> = s.iter().map(|x| x.as_ptr()).collect();
+    let rejected = s.retain_split(|x| x.len() == 1);
+    assert_eq!(&*s, &["a", "c"]);
+    assert_eq!(&*rejected, &["bb", "dd"]);
+    assert_eq!([s[0].as_ptr(), s[1].as_ptr()], [heap[0], heap[2]]);
+    assert_eq!([rejected[0].as_ptr(), rejected[1].as_ptr()], [heap[1], heap[3]]);
+}
+
+#[test]
+fn retain_and_shrink_five_of_a_thousand() {
+    let mut v: SmallerVec<u32, u16> = (0..1000).collect();
+    v.retain_and_shrink(|x| x % 200 == 0);
+    assert_eq!(&*v, &[0, 200, 400, 600, 800]);
+    assert_eq!(v.capacity(), 5);
+}
+
+#[test]
+fn retain_and_shrink_keeps_a_mostly_full_buffer() {
+    let mut w: SmallerVec<u32, u16> = (0..100).collect();
+    let cap = w.capacity();
+    w.retain_and_shrink(|x| *x < 50);
+    assert_eq!(w.len(), 50);
+    assert_eq!(w.capacity(), cap);
+}
+
+#[test]
+fn compaction_chains() {
+    let mut v: SmallerVec<u8, u8> = [1, 1, 2, 3, 3, 4, 4, 4, 5].into_iter().collect();
+    let cap = v.capacity();
+    v.retain(|&x| x != 5).dedup();
+    assert_eq!(&*v, &[1, 2, 3, 4]);
+    assert_eq!(v.capacity(), cap);
+    assert_eq!(v.truncate_rev(3).truncate(2).len(), 2);
+    assert!(v.clear().is_empty());
+
+    let mut w: SmallerVec<(u8, u8), u8> = [(1, 0), (1, 1), (2, 0), (3, 0)].into_iter().collect();
+    w.dedup_by_key_keep_last(|&mut (k, _)| k).retain_and_shrink(|&(k, _)| k != 2);
+    assert_eq!(&*w, &[(1, 1), (3, 0)]);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::allocations;
+use smaller_vec::{ShrinkStrategy, SmallerVec};
+use std::ptr::NonNull;
+
+#[test]
+fn shrink_to_fit_when_already_tight() {
+    let mut v = SmallerVec::<u32, u16>::with_capacity(4);
+    v.extend([1, 2, 3, 4]);
+    let p = v.as_ptr();
+    let (_, n) = allocations(|| v.shrink_to_fit());
+    assert_eq!(n, 0);
+    assert_eq!(v.as_ptr(), p);
+    assert_eq!(v.capacity(), 4);
+}
+
+#[test]
+fn shrink_to_fit_empty_frees_the_buffer() {
+    let mut v: SmallerVec<u32, u16> = (0..10).collect();
+    v.clear();
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 0);
+    assert_eq!(v.as_ptr(), NonNull::<u32>::dangling().as_ptr());
+    v.push(9);
+    assert_eq!(&*v, &[9]);
+}
+
+#[test]
+fn shrink_to_fit_twice() {
+    let mut v = SmallerVec::<u32, u16>::with_capacity(4);
+    v.extend([1, 2, 3, 4, 5]);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 5);
+    let p = v.as_ptr();
+    let (_, n) = allocations(|| v.shrink_to_fit());
+    assert_eq!(n, 0);
+    assert_eq!(v.as_ptr(), p);
+    assert_eq!(&*v, &[1, 2, 3, 4, 5]);
+
+    v.clear();
+    v.shrink_to_fit();
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 0);
+}
+
+#[test]
+fn shrink_strategies_keep_the_contents() {
+    for strategy in [ShrinkStrategy::Auto, ShrinkStrategy::Realloc, ShrinkStrategy::Fresh] {
+        for keep in [0, 3, 700] {
+            let mut v: SmallerVec<String, u16> = (0..1000).map(|i| i.to_string()).collect();
+            v.truncate(keep);
+            v.shrink_to_fit_with(strategy);
+            assert_eq!(v.capacity(), keep);
+            assert!(v.iter().enumerate().all(|(i, s)| *s == i.to_string()));
+            v.push("x".into());
+            assert_eq!(v.len(), keep + 1);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use smaller_vec::SmallerVec;
+
+#[test]
+fn write_to_spare_grows_by_what_was_written() {
+    let mut v = SmallerVec::<u32, u16>::new();
+    v.push(1);
+    unsafe {
+        v.write_to_spare(8, |spare| {
+            assert_eq!(spare.len(), 8);
+            for (i, slot) in spare[..3].iter_mut().enumerate() {
+                slot.write(i as u32 + 10);
+            }
+            3
+        })
+    };
+    assert_eq!(&*v, &[1, 10, 11, 12]);
+}
+
+#[test]
+#[should_panic(expected = "wrote 9 of 8 spare slots")]
+fn write_to_spare_overcounting_panics() {
+    let mut v = SmallerVec::<u32, u16>::new();
+    unsafe { v.write_to_spare(8, |_| 9) };
+}
+
+mod set_len_checked {
+    use smaller_vec::SmallerVec;
+    use std::rc::Rc;
+
+    fn shared(r: &Rc<()>) -> SmallerVec<Rc<()>, u8> {
+        let mut v = SmallerVec::with_capacity(8);
+        v.extend((0..4).map(|_| r.clone()));
+        v
+    }
+
+    #[test]
+    fn shrinking_drops_the_tail() {
+        let r = Rc::new(());
+        let mut v = shared(&r);
+        unsafe { v.set_len_checked(1).unwrap() };
+        assert_eq!(v.len(), 1);
+        assert_eq!(Rc::strong_count(&r), 2);
+    }
+
+    #[test]
+    fn past_capacity_is_an_error() {
+        let r = Rc::new(());
+        let mut v = shared(&r);
+        let cap = v.capacity();
+        assert!(unsafe { v.set_len_checked(cap + 1) }.is_err());
+        assert_eq!(v.len(), 4);
+        assert_eq!(Rc::strong_count(&r), 5);
+    }
+
+    #[test]
+    fn growing_within_capacity() {
+        let r = Rc::new(());
+        let mut v = shared(&r);
+        v.spare_capacity_mut()[0].write(r.clone());
+        unsafe { v.set_len_checked(5).unwrap() };
+        assert_eq!(v.len(), 5);
+        drop(v);
+        assert_eq!(Rc::strong_count(&r), 1);
+    }
+}
+
+#[test]
+fn as_non_null_points_at_the_buffer() {
+    let mut v = SmallerVec::<u32, u8>::with_capacity(4);
+    assert_eq!(v.as_non_null().as_ptr() as *const u32, v.as_ptr());
+    let p = v.non_null_mut();
+    unsafe {
+        p.as_ptr().write(5);
+        p.as_ptr().add(1).write(6);
+        v.set_len(2);
+    }
+    assert_eq!(&*v, &[5, 6]);
+    assert!(SmallerVec::<u64, u8>::new().as_non_null().as_ptr().is_aligned());
+}
+
+#[test]
+fn spare_capacity_len() {
+    let mut v = SmallerVec::<u8, u8>::with_capacity(5);
+    assert_eq!(v.spare_capacity().len(), 5);
+    v.push(1);
+    assert_eq!(v.spare_capacity().len(), 4);
+    v.extend([2, 3, 4, 5]);
+    assert!(v.spare_capacity().is_empty());
+}
+
+#[test]
+fn push_uninit_write_then_read() {
+    let mut v: SmallerVec<String, u8> = SmallerVec::new();
+    v.push("a".into());
+    unsafe { v.push_uninit().write("b".into()) };
+    assert_eq!(&*v, &["a", "b"]);
+    // handed back unwritten
+    unsafe {
+        v.push_uninit();
+        v.set_len(v.len() - 1);
+    }
+    assert_eq!(&*v, &["a", "b"]);
+}
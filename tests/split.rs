@@ -0,0 +1,49 @@
+use smaller_vec::SmallerVec;
+
+fn strings() -> SmallerVec<String, u8> {
+    (0..5).map(|i| i.to_string()).collect()
+}
+
+#[test]
+fn into_split_at_start() {
+    let (a, b) = strings().into_split_at(0);
+    assert!(a.is_empty());
+    assert_eq!(&*b, &["0", "1", "2", "3", "4"]);
+}
+
+#[test]
+fn into_split_at_end() {
+    let (a, b) = strings().into_split_at(5);
+    assert_eq!(&*a, &["0", "1", "2", "3", "4"]);
+    assert!(b.is_empty());
+}
+
+#[test]
+fn into_split_at_middle() {
+    let (a, b) = strings().into_split_at(2);
+    assert_eq!(&*a, &["0", "1"]);
+    assert_eq!(&*b, &["2", "3", "4"]);
+}
+
+#[test]
+fn split_off_small_at_moves_the_buffer_to_the_suffix() {
+    let mut v: SmallerVec<String, u16> = (0..100).map(|i| i.to_string()).collect();
+    let (ptr, cap) = (v.as_ptr(), v.capacity());
+    let tail = v.split_off(3);
+    assert_eq!(tail.as_ptr(), ptr);
+    assert_eq!(tail.capacity(), cap);
+    assert_eq!(&*v, &["0", "1", "2"]);
+    assert_eq!(v.capacity(), 3);
+    assert_eq!(tail.len(), 97);
+    assert_eq!((&*tail[0], &*tail[96]), ("3", "99"));
+}
+
+#[test]
+fn split_off_large_at_keeps_the_buffer() {
+    let mut v: SmallerVec<String, u16> = (0..10).map(|i| i.to_string()).collect();
+    let ptr = v.as_ptr();
+    let tail = v.split_off(8);
+    assert_eq!(v.as_ptr(), ptr);
+    assert_eq!(v.len(), 8);
+    assert_eq!(&*tail, &["8", "9"]);
+}
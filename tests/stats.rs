@@ -0,0 +1,50 @@
+#![cfg(feature = "stats")]
+
+use smaller_vec::stats::{realloc_count, reset_realloc_count};
+use smaller_vec::SmallerVec;
+
+#[test]
+fn thousand_pushes() {
+    reset_realloc_count();
+    let mut v = SmallerVec::<u32, u16>::new();
+    for i in 0..1000 {
+        v.push(i);
+    }
+    // 4, 8, 16, ... 1024
+    assert_eq!(v.capacity(), 1024);
+    assert_eq!(realloc_count(), 9);
+}
+
+#[test]
+fn failed_growth_isnt_counted() {
+    reset_realloc_count();
+    let mut v = SmallerVec::<u8, u8>::new();
+    assert!(v.try_reserve(300).is_err());
+    assert_eq!(realloc_count(), 0);
+    v.shrink_to_fit();
+    assert_eq!(realloc_count(), 0);
+}
+
+#[test]
+fn reserve_one_at_a_time_is_geometric() {
+    reset_realloc_count();
+    let mut v = SmallerVec::<u8, u32>::new();
+    for i in 0..100_000 {
+        v.reserve(1);
+        v.push(i as u8);
+    }
+    // 1, 2, 4, ... 131072, doubling rather than stepping by one
+    assert_eq!(v.capacity(), 131_072);
+    assert_eq!(realloc_count(), 18);
+}
+
+#[test]
+fn extend_all_reserves_once() {
+    let chunks: Vec<&[u8]> = vec![b"abc", b"", b"defgh", b"ij"];
+    let mut v = SmallerVec::<u8, u32>::new();
+    reset_realloc_count();
+    v.extend_all(chunks.iter().map(|c| c.iter().copied()));
+    assert_eq!(realloc_count(), 1);
+    assert_eq!(&*v, b"abcdefghij");
+    assert_eq!(v.capacity(), 10);
+}
@@ -0,0 +1,60 @@
+use smaller_vec::SmallerVec;
+use std::hash::{BuildHasher, RandomState};
+
+#[test]
+fn hash_matches_the_slice() {
+    let s = RandomState::new();
+    let v: SmallerVec<u8> = b"hello".iter().collect();
+    assert_eq!(s.hash_one(&v), s.hash_one(&b"hello"[..]));
+    let w: SmallerVec<u8, u8> = b"hello".iter().collect();
+    assert_eq!(s.hash_one(&w), s.hash_one(&b"hello"[..]));
+}
+
+mod cross_limit {
+    use smaller_vec::SmallerVec;
+    use std::cmp::Ordering;
+    use std::collections::HashMap;
+    use std::hash::{BuildHasher, RandomState};
+
+    #[test]
+    fn eq_and_ord_across_limits() {
+        let a: SmallerVec<i32, u8> = [1, 2, 3].into_iter().collect();
+        let b: SmallerVec<i32, u32> = [1, 2, 3].into_iter().collect();
+        let c: SmallerVec<i32, u16> = [1, 2, 4].into_iter().collect();
+        assert!(a == b);
+        assert!(a != c);
+        assert!(a < c);
+        assert!(c > b);
+        assert_eq!(a.partial_cmp(&b), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn map_keys() {
+        let a: SmallerVec<i32, u8> = [1, 2, 3].into_iter().collect();
+        let b: SmallerVec<i32, u32> = [1, 2, 3].into_iter().collect();
+        let s = RandomState::new();
+        assert_eq!(s.hash_one(&a), s.hash_one(&b));
+
+        let mut m: HashMap<SmallerVec<i32, u8>, u8> = HashMap::new();
+        m.insert(a.clone(), 1);
+        m.insert([4].into_iter().collect(), 2);
+        *m.get_mut(&a).unwrap() += 1;
+        assert_eq!(m[&a], 2);
+        assert_eq!(m.len(), 2);
+    }
+}
+
+#[test]
+fn set_lookup_by_slice() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let mut hashed: HashSet<SmallerVec<i32, u16>> = HashSet::new();
+    hashed.insert([1, 2, 3].into_iter().collect());
+    let key: &[i32] = &[1, 2, 3];
+    assert!(hashed.contains(key));
+    assert!(!hashed.contains(&[1, 2][..]));
+
+    let mut ordered: BTreeSet<SmallerVec<i32, u8>> = BTreeSet::new();
+    ordered.insert([4, 5].into_iter().collect());
+    assert!(ordered.contains(&[4, 5][..]));
+}
@@ -0,0 +1,48 @@
+use smaller_vec::SmallerVec;
+use std::cell::RefCell;
+
+thread_local!(static LOG: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) });
+
+struct Logged(u32);
+
+impl Drop for Logged {
+    fn drop(&mut self) {
+        LOG.with(|l| l.borrow_mut().push(self.0));
+    }
+}
+
+fn dropped() -> Vec<u32> {
+    LOG.with(|l| std::mem::take(&mut *l.borrow_mut()))
+}
+
+fn logged(n: u32) -> SmallerVec<Logged, u8> {
+    (0..n).map(Logged).collect()
+}
+
+#[test]
+fn truncate_drops_front_to_back() {
+    let mut v = logged(6);
+    v.truncate(4);
+    assert_eq!(dropped(), [4, 5]);
+    drop(v);
+    assert_eq!(dropped(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn truncate_rev_drops_back_to_front() {
+    let mut v = logged(6);
+    v.truncate_rev(2);
+    assert_eq!(dropped(), [5, 4, 3, 2]);
+    v.truncate_rev(5);
+    assert!(dropped().is_empty());
+    assert_eq!(v.len(), 2);
+}
+
+#[test]
+fn clear_matches_truncate() {
+    let mut v = logged(3);
+    let cap = v.capacity();
+    v.clear();
+    assert_eq!(dropped(), [0, 1, 2]);
+    assert_eq!(v.capacity(), cap);
+}
@@ -0,0 +1,95 @@
+use smaller_vec::{SmallerVec, TryReserveErrorKind};
+use std::alloc::{GlobalAlloc, Layout, System};
+
+// refuses anything over a GiB, so allocation failure can be tested without
+// actually running out of memory
+struct Refusing;
+
+const REFUSE_OVER: usize = 1 << 30;
+
+unsafe impl GlobalAlloc for Refusing {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() > REFUSE_OVER {
+            std::ptr::null_mut()
+        } else {
+            System.alloc(layout)
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if new_size > REFUSE_OVER {
+            std::ptr::null_mut()
+        } else {
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Refusing = Refusing;
+
+#[test]
+fn try_with_capacity_ok() {
+    let v = SmallerVec::<u64, u16>::try_with_capacity(100).unwrap();
+    assert_eq!(v.capacity(), 100);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn try_with_capacity_past_the_limit() {
+    let e = SmallerVec::<u8, u8>::try_with_capacity(256).unwrap_err();
+    assert_eq!(e.kind(), TryReserveErrorKind::CapacityOverflow { requested: 256, max: 255 });
+}
+
+#[test]
+fn try_with_capacity_allocation_refused() {
+    let e = SmallerVec::<[u8; 1 << 20], u16>::try_with_capacity(2000).unwrap_err();
+    assert!(matches!(e.kind(), TryReserveErrorKind::AllocError { .. }), "{e}");
+}
+
+// each kind on its own, through `try_reserve` and the panicking `reserve`
+
+#[test]
+fn capacity_overflow_kind() {
+    let mut v: SmallerVec<u8, u8> = SmallerVec::new();
+    let e = v.try_reserve(256).unwrap_err();
+    assert_eq!(e.kind(), TryReserveErrorKind::CapacityOverflow { requested: 256, max: 255 });
+    assert_eq!(v.capacity(), 0);
+}
+
+#[test]
+fn allocation_too_large_kind() {
+    // 1 << 24 fits in a u32 but 1 << 64 bytes doesn't fit in an isize
+    let mut v: SmallerVec<[u8; 1 << 40], u32> = SmallerVec::new();
+    let e = v.try_reserve_exact(1 << 24).unwrap_err();
+    assert_eq!(e.kind(), TryReserveErrorKind::AllocationTooLarge { requested: 1 << 24 });
+    assert_eq!(v.capacity(), 0);
+}
+
+#[test]
+fn alloc_error_kind() {
+    let mut v: SmallerVec<u8, u32> = SmallerVec::new();
+    v.push(1);
+    let e = v.try_reserve_exact(REFUSE_OVER + 1).unwrap_err();
+    assert!(matches!(e.kind(), TryReserveErrorKind::AllocError { .. }), "{e}");
+    // a refused realloc leaves the old buffer alone
+    assert_eq!(&*v, &[1]);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn reserve_panics_on_capacity_overflow() {
+    let mut v: SmallerVec<u8, u8> = SmallerVec::new();
+    v.reserve(256);
+}
+
+#[test]
+#[should_panic(expected = "allocation too large")]
+fn reserve_panics_on_allocation_too_large() {
+    let mut v: SmallerVec<[u8; 1 << 40], u32> = SmallerVec::new();
+    v.reserve_exact(1 << 24);
+}
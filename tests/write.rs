@@ -0,0 +1,25 @@
+use smaller_vec::SmallerVec;
+use std::io::{ErrorKind, Write};
+
+#[test]
+fn short_write_at_the_limit() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    assert_eq!(v.write(&[1; 200]).unwrap(), 200);
+    assert_eq!(v.write(&[2; 200]).unwrap(), 55);
+    assert_eq!(v.write(&[3; 2]).unwrap(), 0);
+    assert_eq!(v.len(), 255);
+    assert_eq!(v[254], 2);
+}
+
+#[test]
+fn write_all_past_the_limit() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    assert_eq!(v.write_all(&[0; 300]).unwrap_err().kind(), ErrorKind::WriteZero);
+}
+
+#[test]
+fn write_fmt() {
+    let mut v = SmallerVec::<u8, u8>::new();
+    write!(v, "hi {}", 5).unwrap();
+    assert_eq!(&*v, b"hi 5");
+}
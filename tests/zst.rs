@@ -0,0 +1,32 @@
+use smaller_vec::SmallerVec;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn push_up_to_the_limit() {
+    let mut v: SmallerVec<(), u8> = SmallerVec::new();
+    assert_eq!(v.capacity(), 255);
+    for _ in 0..255 {
+        v.push(());
+    }
+    assert_eq!(v.len(), 255);
+    assert_eq!(v.capacity(), 255);
+
+    let r = catch_unwind(AssertUnwindSafe(|| v.push(())));
+    assert!(r.is_err());
+    assert_eq!(v.len(), 255);
+    assert_eq!(v.into_iter().count(), 255);
+}
+
+#[test]
+fn capacity_is_always_the_limit() {
+    let mut v: SmallerVec<(), u8> = (0..10).map(|_| ()).collect();
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 255);
+    assert_eq!(SmallerVec::<(), u16>::with_capacity(3).capacity(), 65535);
+
+    let w: SmallerVec<(), u16> = v.widen_limit();
+    assert_eq!(w.capacity(), 65535);
+    assert_eq!(w.len(), 10);
+    let x: SmallerVec<(), u8> = w.shrink_limit().unwrap();
+    assert_eq!(x.capacity(), 255);
+}